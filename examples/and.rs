@@ -5,12 +5,12 @@
 //! ```
 //!
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use axum::{response::IntoResponse, routing::get, Router};
 use composable_tower_http::{
     authorize::{
-        api_key::{ApiKey, DefaultApiKeyAuthorizer},
+        api_key::{ApiKey, ApiKeyRecord, DefaultApiKeyAuthorizer},
         basic_auth::{BasicAuthUser, DefaultBasicAuthAuthorizer},
         header::{basic_auth::DefaultBasicAuthExtractor, DefaultHeaderExtractor},
     },
@@ -22,7 +22,7 @@ use composable_tower_http::{
 mod util;
 
 async fn api_key_and_basic_auth(
-    Extracted(And { left, right }): Extracted<And<ApiKey, BasicAuthUser>>,
+    Extracted(And { left, right }): Extracted<And<ApiKeyRecord, BasicAuthUser>>,
 ) -> impl IntoResponse {
     format!("You used the api key: {:?}, and you are: {:?}", left, right)
 }
@@ -31,9 +31,9 @@ async fn api_key_and_basic_auth(
 async fn main() -> anyhow::Result<()> {
     util::init("and")?;
 
-    let valid_api_keys: HashSet<ApiKey> = ["api-key-1", "api-key-2"]
+    let valid_api_keys: HashMap<ApiKey, ApiKeyRecord> = ["api-key-1", "api-key-2"]
         .into_iter()
-        .map(ApiKey::new)
+        .map(|api_key| (ApiKey::new(api_key), ApiKeyRecord::new("owner-1", api_key)))
         .collect();
 
     let api_key_authorizer =
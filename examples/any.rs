@@ -5,12 +5,12 @@
 //! ```
 //!
 
-use std::collections::HashSet;
+use std::collections::HashMap;
 
 use axum::{response::IntoResponse, routing::get, Router};
 use composable_tower_http::{
     authorize::{
-        api_key::{ApiKey, DefaultApiKeyAuthorizer},
+        api_key::{ApiKey, ApiKeyRecord, DefaultApiKeyAuthorizer},
         header::DefaultHeaderExtractor,
     },
     extension::ExtensionLayerExt,
@@ -20,27 +20,27 @@ use composable_tower_http::{
 #[path = "../util/util.rs"]
 mod util;
 
-async fn api_key(Extracted(api_key): Extracted<ApiKey>) -> impl IntoResponse {
-    format!("You used the api key: {}", api_key.value)
+async fn api_key(Extracted(api_key): Extracted<ApiKeyRecord>) -> impl IntoResponse {
+    format!("You used the api key: {}", api_key.name)
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     util::init("any")?;
 
-    let x_valid_api_keys: HashSet<ApiKey> = ["api-key-1-x", "api-key-2-x"]
+    let x_valid_api_keys: HashMap<ApiKey, ApiKeyRecord> = ["api-key-1-x", "api-key-2-x"]
         .into_iter()
-        .map(ApiKey::new)
+        .map(|api_key| (ApiKey::new(api_key), ApiKeyRecord::new("owner-1", api_key)))
         .collect();
 
-    let y_valid_api_keys: HashSet<ApiKey> = ["api-key-1-y", "api-key-2-y"]
+    let y_valid_api_keys: HashMap<ApiKey, ApiKeyRecord> = ["api-key-1-y", "api-key-2-y"]
         .into_iter()
-        .map(ApiKey::new)
+        .map(|api_key| (ApiKey::new(api_key), ApiKeyRecord::new("owner-1", api_key)))
         .collect();
 
-    let z_valid_api_keys: HashSet<ApiKey> = ["api-key-1-z", "api-key-2-z"]
+    let z_valid_api_keys: HashMap<ApiKey, ApiKeyRecord> = ["api-key-1-z", "api-key-2-z"]
         .into_iter()
-        .map(ApiKey::new)
+        .map(|api_key| (ApiKey::new(api_key), ApiKeyRecord::new("owner-1", api_key)))
         .collect();
 
     let x_authorizer =
@@ -5,12 +5,12 @@
 //! ```
 //!
 
-use std::collections::HashSet;
+use std::collections::HashMap;
 
 use axum::{response::IntoResponse, routing::get, Router};
 use composable_tower_http::{
     authorize::{
-        api_key::{ApiKey, DefaultApiKeyAuthorizer},
+        api_key::{ApiKey, ApiKeyRecord, DefaultApiKeyAuthorizer},
         header::DefaultHeaderExtractor,
     },
     extension::ExtensionLayerExt,
@@ -20,7 +20,7 @@ use composable_tower_http::{
 #[path = "../util/util.rs"]
 mod util;
 
-async fn api_key(Extracted(api_key): Extracted<ApiKey>) -> impl IntoResponse {
+async fn api_key(Extracted(api_key): Extracted<ApiKeyRecord>) -> impl IntoResponse {
     format!("You used the api key: {:?}", api_key)
 }
 
@@ -28,9 +28,9 @@ async fn api_key(Extracted(api_key): Extracted<ApiKey>) -> impl IntoResponse {
 async fn main() -> anyhow::Result<()> {
     util::init("api_key")?;
 
-    let valid_api_keys: HashSet<ApiKey> = ["api-key-1", "api-key-2"]
+    let valid_api_keys: HashMap<ApiKey, ApiKeyRecord> = ["api-key-1", "api-key-2"]
         .into_iter()
-        .map(ApiKey::new)
+        .map(|api_key| (ApiKey::new(api_key), ApiKeyRecord::new("owner-1", api_key)))
         .collect();
 
     let layer =
@@ -0,0 +1,56 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example basic_auth_hashed --features="axum"
+//! ```
+//!
+//! Set `HTPASSWD` to load users from a file created with `htpasswd -B`.
+//!
+
+use anyhow::Context;
+use axum::{response::IntoResponse, routing::get, Router};
+use composable_tower_http::{
+    authorize::{
+        basic_auth::{
+            BasicAuthUser, DefaultBasicAuthAuthorizer, HashedCredentialStore, PasswordHash,
+        },
+        header::basic_auth::DefaultBasicAuthExtractor,
+    },
+    extension::ExtensionLayerExt,
+    extract::Extracted,
+};
+
+#[path = "../util/util.rs"]
+mod util;
+
+async fn basic_auth(Extracted(user): Extracted<BasicAuthUser>) -> impl IntoResponse {
+    format!("You are: {}", user.username)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("basic_auth_hashed")?;
+
+    let credential_store = match std::env::var("HTPASSWD") {
+        Ok(path) => HashedCredentialStore::from_htpasswd_file(path)
+            .await
+            .context("Failed to load htpasswd file")?,
+        Err(_) => HashedCredentialStore::new()
+            .user("user-1", PasswordHash::argon2id("password-1")?)
+            .user("user-2", PasswordHash::bcrypt("password-2", 10)?),
+    };
+
+    tracing::info!(users = credential_store.len());
+
+    let layer = DefaultBasicAuthAuthorizer::new(DefaultBasicAuthExtractor::new(), credential_store)
+        .extension_layer();
+
+    let app = Router::new()
+        // curl -u "user-1:password-1" localhost:5000
+        .route("/", get(basic_auth))
+        .layer(layer)
+        // curl -u "user-1:wrong" localhost:5000
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
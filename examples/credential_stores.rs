@@ -0,0 +1,108 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example credential_stores --features="axum"
+//! ```
+//!
+//! API keys are read from `API_KEYS` or from `api_keys.json` in the temp directory.
+//! Edit the file while the example runs to add or revoke keys.
+//!
+
+use std::{collections::HashMap, convert::Infallible, time::Duration};
+
+use axum::{response::IntoResponse, routing::get, Router};
+use composable_tower_http::{
+    authorize::{
+        api_key::{parse_api_keys, ApiKeyRecord, DefaultApiKeyAuthorizer},
+        basic_auth::{BasicAuthUser, DefaultBasicAuthAuthorizer, PasswordHash, UserLookup},
+        header::{basic_auth::DefaultBasicAuthExtractor, DefaultHeaderExtractor},
+        store::{CachedStore, WatchedFile},
+    },
+    extension::ExtensionLayerExt,
+    extract::Extracted,
+};
+
+#[path = "../util/util.rs"]
+mod util;
+
+/// Stands in for a database.
+struct SlowUserLookup {
+    users: HashMap<String, PasswordHash>,
+}
+
+impl UserLookup for SlowUserLookup {
+    type Error = Infallible;
+
+    async fn find_password_hash(&self, username: &str) -> Result<Option<PasswordHash>, Infallible> {
+        tracing::info!(username, "Looking up user");
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        Ok(self.users.get(username).cloned())
+    }
+}
+
+async fn api_key(Extracted(api_key): Extracted<ApiKeyRecord>) -> impl IntoResponse {
+    format!(
+        "You used the api key {} of {}",
+        api_key.name, api_key.owner_id
+    )
+}
+
+async fn basic_auth(Extracted(user): Extracted<BasicAuthUser>) -> impl IntoResponse {
+    format!("You are: {}", user.username)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("credential_stores")?;
+
+    let path = match std::env::var("API_KEYS") {
+        Ok(path) => path.into(),
+        Err(_) => {
+            let path = std::env::temp_dir().join("api_keys.json");
+
+            tokio::fs::write(
+                &path,
+                r#"[{ "key": "api-key-1", "owner_id": "user-1", "name": "key-1" }]"#,
+            )
+            .await?;
+
+            path
+        }
+    };
+
+    tracing::info!(?path, "Watching API keys");
+
+    let api_keys = WatchedFile::new(path, 1, parse_api_keys).await?;
+
+    let api_key_layer =
+        DefaultApiKeyAuthorizer::new(DefaultHeaderExtractor::new("x-api-key"), api_keys)
+            .extension_layer();
+
+    let users = SlowUserLookup {
+        users: HashMap::from([(
+            String::from("user-1"),
+            PasswordHash::bcrypt("password-1", 10)?,
+        )]),
+    };
+
+    let users = CachedStore::new(users)
+        .time_to_live_in_seconds(30)
+        .negative_time_to_live_in_seconds(5);
+
+    let basic_auth_layer =
+        DefaultBasicAuthAuthorizer::new(DefaultBasicAuthExtractor::new(), users).extension_layer();
+
+    let app = Router::new()
+        // curl -H "x-api-key: api-key-1" localhost:5000
+        // echo '[{ "key": "api-key-2", "owner_id": "user-2", "name": "key-2" }]' > /tmp/api_keys.json
+        // curl -H "x-api-key: api-key-2" localhost:5000
+        .route("/", get(api_key).layer(api_key_layer))
+        // curl -u "user-1:password-1" localhost:5000/basic
+        // The second request is served from the cache.
+        .route("/basic", get(basic_auth).layer(basic_auth_layer))
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
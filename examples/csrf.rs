@@ -0,0 +1,51 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example csrf --features="axum"
+//! ```
+//!
+
+use axum::{response::IntoResponse, routing::get, Extension, Router};
+use composable_tower_http::{
+    authorize::session::{SameSite, SessionCookie},
+    extension::{CsrfConfig, CsrfLayerExt, CsrfToken},
+};
+
+#[path = "../util/util.rs"]
+mod util;
+
+async fn token(Extension(token): Extension<CsrfToken>) -> impl IntoResponse {
+    format!(
+        "Send this token in the x-csrf-token header: {}",
+        token.as_str()
+    )
+}
+
+async fn transfer() -> impl IntoResponse {
+    "Transfer accepted"
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("csrf")?;
+
+    // Secure cookies are not sent over plain http.
+    let csrf_layer = CsrfConfig::with_cookie(
+        SessionCookie::new("csrf_token")?
+            .secure(false)
+            .http_only(false)
+            .same_site(Some(SameSite::Strict)),
+    )
+    .allowed_origin("http://localhost:5000")
+    .csrf_layer();
+
+    let app = Router::new()
+        // curl -i localhost:5000
+        // curl -X POST -H "Origin: http://localhost:5000" -H "Cookie: csrf_token=<token>" -H "x-csrf-token: <token>" localhost:5000
+        // curl -X POST -H "Origin: https://evil.com" -H "Cookie: csrf_token=<token>" -H "x-csrf-token: <token>" localhost:5000
+        .route("/", get(token).post(transfer))
+        .layer(csrf_layer)
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
@@ -0,0 +1,55 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example digest --features="axum"
+//! ```
+//!
+
+use std::num::NonZeroUsize;
+
+use axum::{response::IntoResponse, routing::get, Router};
+use composable_tower_http::{
+    authorize::{
+        digest::{nonce::InMemoryNonceStore, DefaultDigestAuthorizerBuilder, DigestUser},
+        header::digest::{DefaultDigestExtractor, DigestAlgorithm},
+    },
+    extension::ExtensionLayerExt,
+    extract::Extracted,
+};
+
+#[path = "../util/util.rs"]
+mod util;
+
+async fn digest(Extracted(user): Extracted<DigestUser>) -> impl IntoResponse {
+    format!("You are logged in as: {}", user.username)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("digest")?;
+
+    let nonce_store = InMemoryNonceStore::new(
+        5 * 60,
+        NonZeroUsize::new(10_000).expect("Capacity is not zero"),
+    );
+
+    let layer =
+        DefaultDigestAuthorizerBuilder::new(DefaultDigestExtractor::new(), nonce_store, "devices")
+            // MD5 is only offered for legacy devices.
+            .algorithms(&[DigestAlgorithm::Sha256, DigestAlgorithm::Md5])
+            .users([
+                DigestUser::new("device-1", "password-1"),
+                DigestUser::new("device-2", "password-2"),
+            ])
+            .build()
+            .extension_layer();
+
+    let app = Router::new()
+        // curl --digest -u device-1:password-1 localhost:5000
+        .route("/", get(digest))
+        .layer(layer)
+        // curl --digest -u device-1:wrong localhost:5000
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
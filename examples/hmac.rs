@@ -0,0 +1,57 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example hmac --features="axum"
+//! ```
+//!
+
+use axum::{response::IntoResponse, routing::post, Router};
+use composable_tower_http::{
+    authorize::{
+        header::DefaultHeaderExtractor,
+        hmac::{
+            DefaultHmacSignatureAuthorizer, HmacSecret, HmacSignatureValidation,
+            VerifiedHmacSignature,
+        },
+    },
+    extension::BodyExtensionLayerExt,
+    extract::Extracted,
+};
+
+#[path = "../util/util.rs"]
+mod util;
+
+async fn webhook(
+    Extracted(verified): Extracted<VerifiedHmacSignature>,
+    body: String,
+) -> impl IntoResponse {
+    format!(
+        "Signed with secret {}, you sent: {}",
+        verified.secret_id, body
+    )
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("hmac")?;
+
+    let secrets = vec![
+        HmacSecret::new("current", b"It's a Secret to Everybody".as_slice()),
+        HmacSecret::new("previous", b"It was a Secret to Everybody".as_slice()),
+    ];
+
+    let layer = DefaultHmacSignatureAuthorizer::new(
+        DefaultHeaderExtractor::new("x-hub-signature-256"),
+        secrets,
+        HmacSignatureValidation::new().signature_prefix("sha256="),
+    )
+    .body_extension_layer(64 * 1024);
+
+    let app = Router::new()
+        // curl -H "x-hub-signature-256: sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17" -d "Hello, World!" localhost:5000
+        .route("/", post(webhook))
+        .layer(layer)
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
@@ -0,0 +1,64 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example introspection --features="axum"
+//! ```
+//!
+
+use axum::{response::IntoResponse, routing::get, Json, Router};
+use composable_tower_http::{
+    authorize::{
+        header::bearer::DefaultBearerExtractor,
+        introspection::{introspect::HttpTokenIntrospector, DefaultIntrospectionAuthorizerBuilder},
+    },
+    extension::ExtensionLayerExt,
+    extract::Extracted,
+};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+#[path = "../util/util.rs"]
+mod util;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub client_id: String,
+    pub scope: String,
+    pub exp: u64,
+}
+
+async fn claims(Extracted(claims): Extracted<Claims>) -> impl IntoResponse {
+    Json(claims)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("introspection")?;
+
+    let introspection_uri = std::env::var("INTROSPECTION_URI").unwrap_or_else(|_| {
+        String::from("https://keycloak.com/realms/master/protocol/openid-connect/token/introspect")
+    });
+
+    let client_id = std::env::var("CLIENT_ID").unwrap_or_else(|_| String::from("resource-server"));
+
+    let client_secret =
+        std::env::var("CLIENT_SECRET").unwrap_or_else(|_| String::from("client-secret"));
+
+    tracing::info!(%introspection_uri, %client_id);
+
+    let layer = DefaultIntrospectionAuthorizerBuilder::new(
+        DefaultBearerExtractor::new(),
+        HttpTokenIntrospector::new(introspection_uri, client_id, client_secret, Client::new()),
+    )
+    .build::<Claims>()
+    .extension_layer();
+
+    let app = Router::new()
+        // curl -H "Authorization: Bearer <token>" localhost:5000
+        .route("/", get(claims))
+        .layer(layer)
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
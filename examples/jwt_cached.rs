@@ -0,0 +1,79 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example jwt_cached --features="axum"
+//! ```
+//!
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use anyhow::Context;
+use axum::{response::IntoResponse, routing::get, Json, Router};
+use composable_tower_http::{
+    authorize::{
+        header::bearer::DefaultBearerExtractor,
+        jwt::{
+            jwk_set::{fetch::HttpJwkSetFetcher, rotating::RotatingJwkSetProvider},
+            DefaultJwtAuthorizerBuilder, Validation,
+        },
+    },
+    extension::ExtensionLayerExt,
+    extract::{CacheConfig, Extracted, ExtractorExt},
+};
+use http::header::AUTHORIZATION;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+#[path = "../util/util.rs"]
+mod util;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub exp: u64,
+    pub name: String,
+    pub email: String,
+}
+
+async fn claims(Extracted(claims): Extracted<Claims>) -> impl IntoResponse {
+    Json(claims)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("jwt_cached")?;
+
+    let jwks_uri = std::env::var("JWKS_URI").unwrap_or_else(|_| {
+        String::from("https://keycloak.com/realms/master/protocol/openid-connect/certs")
+    });
+
+    let iss =
+        std::env::var("ISS").unwrap_or_else(|_| String::from("https://keycloak.com/realms/master"));
+
+    tracing::info!(%jwks_uri, %iss);
+
+    // Validated claims are cached per token until the token expires or 5 minutes have passed.
+    // Rejected tokens are remembered for 10 seconds.
+    let cache_config = CacheConfig::new(AUTHORIZATION.as_str())
+        .time_to_live_in_seconds(300)
+        .negative_time_to_live_in_seconds(10)
+        .expires_at(|claims: &Claims| UNIX_EPOCH.checked_add(Duration::from_secs(claims.exp)));
+
+    let layer = DefaultJwtAuthorizerBuilder::new(
+        DefaultBearerExtractor::new(),
+        RotatingJwkSetProvider::new(30, HttpJwkSetFetcher::new(jwks_uri, Client::new()))
+            .await
+            .context("Failed to create jwk set provider")?,
+        Validation::new().aud(&["account"]).iss(&[iss]),
+    )
+    .build::<Claims>()
+    .cached(cache_config)
+    .extension_layer();
+
+    let app = Router::new()
+        // curl -H "Authorization: Bearer <token>" localhost:5000
+        .route("/", get(claims))
+        .layer(layer)
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
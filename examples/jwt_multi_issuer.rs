@@ -0,0 +1,72 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example jwt_multi_issuer --features="axum"
+//! ```
+//!
+
+use anyhow::Context;
+use axum::{response::IntoResponse, routing::get, Json, Router};
+use composable_tower_http::{
+    authorize::{
+        header::bearer::DefaultBearerExtractor,
+        jwt::{
+            jwk_set::{fetch::OidcDiscoveryJwkSetFetcher, rotating::RotatingJwkSetProvider},
+            MultiIssuerJwtAuthorizerBuilder,
+        },
+    },
+    extension::ExtensionLayerExt,
+    extract::Extracted,
+};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+#[path = "../util/util.rs"]
+mod util;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub iss: String,
+    pub sub: String,
+}
+
+async fn claims(Extracted(claims): Extracted<Claims>) -> impl IntoResponse {
+    Json(claims)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("jwt_multi_issuer")?;
+
+    let issuers = std::env::var("ISSUERS").unwrap_or_else(|_| {
+        String::from("https://keycloak.com/realms/a,https://keycloak.com/realms/b")
+    });
+
+    let mut builder = MultiIssuerJwtAuthorizerBuilder::new(DefaultBearerExtractor::new());
+
+    for iss in issuers.split(',') {
+        tracing::info!(%iss, "Adding issuer");
+
+        let jwk_set_fetcher = OidcDiscoveryJwkSetFetcher::new(iss.to_string(), Client::new())
+            .await
+            .context("Failed to discover OpenID provider metadata")?;
+
+        let validation = jwk_set_fetcher.validation().validate_aud(false);
+
+        let jwk_set_provider = RotatingJwkSetProvider::new(30, jwk_set_fetcher)
+            .await
+            .context("Failed to create jwk set provider")?;
+
+        builder = builder.issuer(iss, jwk_set_provider, validation);
+    }
+
+    let layer = builder.build::<Claims>().extension_layer();
+
+    let app = Router::new()
+        // curl -H "Authorization: Bearer <token from any issuer>" localhost:5000
+        .route("/", get(claims))
+        .layer(layer)
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
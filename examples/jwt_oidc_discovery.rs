@@ -0,0 +1,76 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example jwt_oidc_discovery --features="axum"
+//! ```
+//!
+
+use anyhow::Context;
+use axum::{response::IntoResponse, routing::get, Json, Router};
+use composable_tower_http::{
+    authorize::{
+        header::bearer::DefaultBearerExtractor,
+        jwt::{
+            jwk_set::{fetch::OidcDiscoveryJwkSetFetcher, rotating::RotatingJwkSetProvider},
+            DefaultJwtAuthorizerBuilder,
+        },
+    },
+    extension::ExtensionLayerExt,
+    extract::Extracted,
+};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+#[path = "../util/util.rs"]
+mod util;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub email_verified: bool,
+    pub name: String,
+    pub preferred_username: String,
+    pub given_name: String,
+    pub family_name: String,
+    pub email: String,
+}
+
+async fn claims(Extracted(claims): Extracted<Claims>) -> impl IntoResponse {
+    Json(claims)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("jwt_oidc_discovery")?;
+
+    let iss =
+        std::env::var("ISS").unwrap_or_else(|_| String::from("https://keycloak.com/realms/master"));
+
+    tracing::info!(%iss);
+
+    let jwk_set_fetcher = OidcDiscoveryJwkSetFetcher::new(iss, Client::new())
+        .await
+        .context("Failed to discover OpenID provider metadata")?;
+
+    // The issuer is taken from the discovery metadata.
+    let validation = jwk_set_fetcher.validation().aud(&["account"]);
+
+    tracing::info!(metadata = ?jwk_set_fetcher.metadata());
+
+    let layer = DefaultJwtAuthorizerBuilder::new(
+        DefaultBearerExtractor::new(),
+        RotatingJwkSetProvider::new(30, jwk_set_fetcher)
+            .await
+            .context("Failed to create jwk set provider")?,
+        validation,
+    )
+    .build::<Claims>()
+    .extension_layer();
+
+    let app = Router::new()
+        // curl -H "Authorization: Bearer <token>" localhost:5000
+        .route("/", get(claims))
+        .layer(layer)
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
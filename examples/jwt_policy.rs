@@ -0,0 +1,86 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example jwt_policy --features="axum"
+//! ```
+//!
+
+use anyhow::Context;
+use axum::{extract::Path, response::IntoResponse, routing::get, Router};
+use composable_tower_http::{
+    authorize::{
+        header::bearer::DefaultBearerExtractor,
+        jwt::{
+            jwk_set::{fetch::HttpJwkSetFetcher, rotating::RotatingJwkSetProvider},
+            DefaultJwtAuthorizerBuilder, Validation,
+        },
+    },
+    extension::{ExtensionLayerExt, Policy, PolicyContext, PolicyLayerExt, Rule, TimeOfDay},
+};
+use http::{Method, StatusCode};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+#[path = "../util/util.rs"]
+mod util;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub preferred_username: String,
+    pub tenant: String,
+}
+
+async fn orders(Path(tenant): Path<String>) -> impl IntoResponse {
+    format!("Orders of tenant {tenant}")
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("jwt_policy")?;
+
+    let jwks_uri = std::env::var("JWKS_URI").unwrap_or_else(|_| {
+        String::from("https://keycloak.com/realms/master/protocol/openid-connect/certs")
+    });
+
+    let iss =
+        std::env::var("ISS").unwrap_or_else(|_| String::from("https://keycloak.com/realms/master"));
+
+    tracing::info!(%jwks_uri, %iss);
+
+    let auth_layer = DefaultJwtAuthorizerBuilder::new(
+        DefaultBearerExtractor::new(),
+        RotatingJwkSetProvider::new(30, HttpJwkSetFetcher::new(jwks_uri, Client::new()))
+            .await
+            .context("Failed to create jwk set provider")?,
+        Validation::new().aud(&["account"]).iss(&[iss]),
+    )
+    .build::<Claims>()
+    .extension_layer();
+
+    // Rules are evaluated in order, the first applying rule decides.
+    let policy_layer = Policy::<Claims>::new("orders")
+        .rule(
+            Rule::deny("maintenance-window")
+                .method(Method::POST)
+                .time_of_day(TimeOfDay::new(2, 0), TimeOfDay::new(3, 0)),
+        )
+        .rule(
+            Rule::allow("own-tenant")
+                .path("/tenants/{tenant}/orders")
+                .when(|ctx: &PolicyContext<'_, Claims>| {
+                    ctx.path_param("tenant") == Some(ctx.principal().tenant.as_str())
+                }),
+        )
+        // Do not reveal that other tenants' orders exist.
+        .deny_status(StatusCode::NOT_FOUND)
+        .policy_layer();
+
+    let app = Router::new()
+        // curl -H "Authorization: Bearer <token>" localhost:5000/tenants/<tenant>/orders
+        .route("/tenants/:tenant/orders", get(orders).post(orders))
+        .layer(policy_layer)
+        .layer(auth_layer)
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
@@ -0,0 +1,94 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example jwt_rbac --features="axum"
+//! ```
+//!
+
+use std::{borrow::Cow, sync::Arc};
+
+use anyhow::Context;
+use axum::{response::IntoResponse, routing::get, Json, Router};
+use composable_tower_http::{
+    authorize::{
+        header::bearer::DefaultBearerExtractor,
+        jwt::{
+            jwk_set::{fetch::HttpJwkSetFetcher, rotating::RotatingJwkSetProvider},
+            DefaultJwtAuthorizerBuilder, Validation,
+        },
+        rbac::{HasRoles, KeycloakRoles, PermissionValidator, RoleRegistry},
+    },
+    extension::{ExtensionLayerExt, ModificationLayerExt},
+    extract::Extracted,
+};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+#[path = "../util/util.rs"]
+mod util;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub preferred_username: String,
+    #[serde(flatten)]
+    pub keycloak: KeycloakRoles,
+}
+
+impl HasRoles for Claims {
+    fn roles(&self) -> Vec<Cow<'_, str>> {
+        self.keycloak.roles()
+    }
+}
+
+async fn claims(Extracted(claims): Extracted<Claims>) -> impl IntoResponse {
+    Json(claims)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("jwt_rbac")?;
+
+    let jwks_uri = std::env::var("JWKS_URI").unwrap_or_else(|_| {
+        String::from("https://keycloak.com/realms/master/protocol/openid-connect/certs")
+    });
+
+    let iss =
+        std::env::var("ISS").unwrap_or_else(|_| String::from("https://keycloak.com/realms/master"));
+
+    tracing::info!(%jwks_uri, %iss);
+
+    let auth_layer = DefaultJwtAuthorizerBuilder::new(
+        DefaultBearerExtractor::new(),
+        RotatingJwkSetProvider::new(30, HttpJwkSetFetcher::new(jwks_uri, Client::new()))
+            .await
+            .context("Failed to create jwk set provider")?,
+        Validation::new().aud(&["account"]).iss(&[iss]),
+    )
+    .build::<Claims>()
+    .extension_layer();
+
+    // Realm roles are prefixed with `realm:`, client roles from `resource_access` with `client:` and the client id.
+    let registry = Arc::new(
+        RoleRegistry::new()
+            .role("realm:user", ["orders:read"])
+            .role("realm:admin", ["orders:write"])
+            .role("client:account:manage-account", ["account:write"])
+            .inherits("realm:admin", ["realm:user"]),
+    );
+
+    let read_layer =
+        PermissionValidator::new(registry.clone(), "orders:read").modification_layer::<Claims>();
+
+    let write_layer =
+        PermissionValidator::new(registry, "orders:write").modification_layer::<Claims>();
+
+    let app = Router::new()
+        // curl -H "Authorization: Bearer <token>" localhost:5000/orders
+        .route("/orders", get(claims).layer(read_layer))
+        // curl -H "Authorization: Bearer <token>" localhost:5000/orders/write
+        .route("/orders/write", get(claims).layer(write_layer))
+        .layer(auth_layer)
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
@@ -0,0 +1,84 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example jwt_scopes --features="axum"
+//! ```
+//!
+
+use anyhow::Context;
+use axum::{response::IntoResponse, routing::get, Json, Router};
+use composable_tower_http::{
+    authorize::{
+        header::bearer::DefaultBearerExtractor,
+        jwt::{
+            jwk_set::{fetch::HttpJwkSetFetcher, rotating::RotatingJwkSetProvider},
+            DefaultJwtAuthorizerBuilder, Validation,
+        },
+        scope::{HasScopes, ScopeValidator, Scopes},
+    },
+    extension::{ExtensionLayerExt, ModificationLayerExt},
+    extract::Extracted,
+};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+#[path = "../util/util.rs"]
+mod util;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    // Keycloak uses the `scope` string, Azure AD the `scp` array.
+    #[serde(alias = "scp")]
+    pub scope: Scopes,
+}
+
+impl HasScopes for Claims {
+    fn scopes(&self) -> &Scopes {
+        &self.scope
+    }
+}
+
+async fn claims(Extracted(claims): Extracted<Claims>) -> impl IntoResponse {
+    Json(claims)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("jwt_scopes")?;
+
+    let jwks_uri = std::env::var("JWKS_URI").unwrap_or_else(|_| {
+        String::from("https://keycloak.com/realms/master/protocol/openid-connect/certs")
+    });
+
+    let iss =
+        std::env::var("ISS").unwrap_or_else(|_| String::from("https://keycloak.com/realms/master"));
+
+    tracing::info!(%jwks_uri, %iss);
+
+    let auth_layer = DefaultJwtAuthorizerBuilder::new(
+        DefaultBearerExtractor::new(),
+        RotatingJwkSetProvider::new(30, HttpJwkSetFetcher::new(jwks_uri, Client::new()))
+            .await
+            .context("Failed to create jwk set provider")?,
+        Validation::new().aud(&["account"]).iss(&[iss]),
+    )
+    .build::<Claims>()
+    .extension_layer();
+
+    let read_layer =
+        ScopeValidator::any_of(["read:orders", "write:orders"]).modification_layer::<Claims>();
+
+    let write_layer =
+        ScopeValidator::all_of(["read:orders", "write:orders"]).modification_layer::<Claims>();
+
+    let app = Router::new()
+        // curl -H "Authorization: Bearer <token>" localhost:5000/orders
+        .route("/orders", get(claims).layer(read_layer))
+        // curl -H "Authorization: Bearer <token>" localhost:5000/orders/write
+        .route("/orders/write", get(claims).layer(write_layer))
+        .layer(auth_layer)
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
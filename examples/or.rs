@@ -5,12 +5,12 @@
 //! ```
 //!
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use axum::{response::IntoResponse, routing::get, Router};
 use composable_tower_http::{
     authorize::{
-        api_key::{ApiKey, DefaultApiKeyAuthorizer},
+        api_key::{ApiKey, ApiKeyRecord, DefaultApiKeyAuthorizer},
         basic_auth::{BasicAuthUser, DefaultBasicAuthAuthorizer},
         header::{basic_auth::DefaultBasicAuthExtractor, DefaultHeaderExtractor},
     },
@@ -22,7 +22,7 @@ use composable_tower_http::{
 mod util;
 
 async fn api_key_or_basic_auth(
-    Extracted(or): Extracted<Or<ApiKey, BasicAuthUser>>,
+    Extracted(or): Extracted<Or<ApiKeyRecord, BasicAuthUser>>,
 ) -> impl IntoResponse {
     match or {
         Or::Left(api_key) => format!("You used the api key: {:?}", api_key),
@@ -34,9 +34,9 @@ async fn api_key_or_basic_auth(
 async fn main() -> anyhow::Result<()> {
     util::init("or")?;
 
-    let valid_api_keys: HashSet<ApiKey> = ["api-key-1", "api-key-2"]
+    let valid_api_keys: HashMap<ApiKey, ApiKeyRecord> = ["api-key-1", "api-key-2"]
         .into_iter()
-        .map(ApiKey::new)
+        .map(|api_key| (ApiKey::new(api_key), ApiKeyRecord::new("owner-1", api_key)))
         .collect();
 
     let api_key_authorizer =
@@ -8,13 +8,13 @@
 //! The endpoint `/` can be accessed with either (a valid JWT) or (an API key and basic auth).
 //!
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::Context;
 use axum::{response::IntoResponse, routing::get, Router};
 use composable_tower_http::{
     authorize::{
-        api_key::{ApiKey, DefaultApiKeyAuthorizer},
+        api_key::{ApiKey, ApiKeyRecord, DefaultApiKeyAuthorizer},
         basic_auth::{BasicAuthUser, DefaultBasicAuthAuthorizer},
         header::{
             basic_auth::DefaultBasicAuthExtractor, bearer::DefaultBearerExtractor,
@@ -45,7 +45,7 @@ pub struct Claims {
 }
 
 async fn index(
-    Extracted(or): Extracted<Or<Claims, And<ApiKey, BasicAuthUser>>>,
+    Extracted(or): Extracted<Or<Claims, And<ApiKeyRecord, BasicAuthUser>>>,
 ) -> impl IntoResponse {
     match or {
         Or::Left(claims) => format!("You used a JWT, claims: {:?}", claims),
@@ -59,9 +59,9 @@ async fn index(
 async fn main() -> anyhow::Result<()> {
     util::init("or_and")?;
 
-    let valid_api_keys: HashSet<ApiKey> = ["api-key-1", "api-key-2"]
+    let valid_api_keys: HashMap<ApiKey, ApiKeyRecord> = ["api-key-1", "api-key-2"]
         .into_iter()
-        .map(ApiKey::new)
+        .map(|api_key| (ApiKey::new(api_key), ApiKeyRecord::new("owner-1", api_key)))
         .collect();
 
     let api_key_authorizer =
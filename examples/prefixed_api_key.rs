@@ -0,0 +1,53 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example prefixed_api_key --features="axum"
+//! ```
+//!
+//! The minted keys are logged on startup. Only their hashes are kept.
+//!
+
+use axum::{response::IntoResponse, routing::get, Router};
+use composable_tower_http::{
+    authorize::{
+        api_key::{ApiKeyRecord, DefaultApiKeyAuthorizer, HashedApiKeyStore},
+        header::DefaultHeaderExtractor,
+    },
+    extension::ExtensionLayerExt,
+    extract::Extracted,
+};
+
+#[path = "../util/util.rs"]
+mod util;
+
+async fn api_key(Extracted(api_key): Extracted<ApiKeyRecord>) -> impl IntoResponse {
+    format!(
+        "You used the api key {} of {}",
+        api_key.name, api_key.owner_id
+    )
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("prefixed_api_key")?;
+
+    let mut store = HashedApiKeyStore::new("acme")?;
+
+    for (owner_id, name) in [("user-1", "ci"), ("user-2", "cli")] {
+        let key = store.mint(ApiKeyRecord::new(owner_id, name).scope("orders:read"));
+
+        tracing::info!(owner_id, name, %key, "Minted API key");
+    }
+
+    let layer = DefaultApiKeyAuthorizer::new(DefaultHeaderExtractor::new("x-api-key"), store)
+        .extension_layer();
+
+    let app = Router::new()
+        // curl -H "x-api-key: <minted key>" localhost:5000
+        .route("/", get(api_key))
+        .layer(layer)
+        // curl -H "x-api-key: acme_000000000000_00000000000000000000000000000000000000" localhost:5000
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
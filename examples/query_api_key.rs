@@ -0,0 +1,98 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example query_api_key --features="axum"
+//! ```
+//!
+
+use std::collections::HashSet;
+
+use axum::{
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use composable_tower_http::{
+    authorize::api_key::ApiKey,
+    extension::ExtensionLayerExt,
+    extract::{Extracted, Extractor},
+};
+use http::{request::Parts, StatusCode};
+
+#[path = "../util/util.rs"]
+mod util;
+
+async fn api_key(Extracted(api_key): Extracted<ApiKey>) -> impl IntoResponse {
+    format!("You used the api key: {:?}", api_key)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("query_api_key")?;
+
+    let valid_api_keys: HashSet<ApiKey> = ["api-key-1", "api-key-2"]
+        .into_iter()
+        .map(ApiKey::new)
+        .collect();
+
+    let layer = QueryApiKeyAuthorizer::new(valid_api_keys).extension_layer();
+
+    let app = Router::new()
+        // curl "localhost:5000?api_key=api-key-1"
+        .route("/", get(api_key))
+        .layer(layer)
+        // curl "localhost:5000?api_key=wrong"
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
+
+#[derive(Debug, Clone)]
+struct QueryApiKeyAuthorizer {
+    valid_api_keys: HashSet<ApiKey>,
+}
+
+impl QueryApiKeyAuthorizer {
+    fn new(valid_api_keys: HashSet<ApiKey>) -> Self {
+        Self { valid_api_keys }
+    }
+}
+
+impl Extractor for QueryApiKeyAuthorizer {
+    type Extracted = ApiKey;
+
+    type Error = QueryApiKeyError;
+
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+        let used_api_key = parts
+            .uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("api_key="))
+            .map(|value| ApiKey::new(value.to_string()))
+            .ok_or(QueryApiKeyError)?;
+
+        if self.valid_api_keys.contains(&used_api_key) {
+            return Ok(used_api_key);
+        }
+
+        Err(QueryApiKeyError)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid or missing api key")]
+struct QueryApiKeyError;
+
+impl IntoResponse for QueryApiKeyError {
+    fn into_response(self) -> Response {
+        StatusCode::UNAUTHORIZED.into_response()
+    }
+}
+
+impl From<QueryApiKeyError> for Response {
+    fn from(value: QueryApiKeyError) -> Self {
+        value.into_response()
+    }
+}
@@ -0,0 +1,62 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example session --features="axum"
+//! ```
+//!
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    routing::{get, post},
+    Router,
+};
+use composable_tower_http::{
+    authorize::{
+        header::cookie::CookieExtractor,
+        session::{store::InMemorySessionStore, SessionAuthorizerBuilder},
+    },
+    extension::ExtensionLayerExt,
+    extract::Extracted,
+};
+use http::header::SET_COOKIE;
+
+#[path = "../util/util.rs"]
+mod util;
+
+async fn login(State(session_store): State<InMemorySessionStore<String>>) -> impl IntoResponse {
+    let id = session_store.create(String::from("user"));
+
+    [(
+        SET_COOKIE,
+        format!("session={id}; Path=/; HttpOnly; SameSite=Lax"),
+    )]
+}
+
+async fn me(Extracted(user): Extracted<String>) -> impl IntoResponse {
+    format!("You are logged in as: {user}")
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("session")?;
+
+    let session_store = InMemorySessionStore::<String>::new();
+
+    let layer =
+        SessionAuthorizerBuilder::new(CookieExtractor::new("session"), session_store.clone())
+            .idle_timeout_in_seconds(15 * 60)
+            .absolute_timeout_in_seconds(8 * 60 * 60)
+            .build()
+            .extension_layer();
+
+    let app = Router::new()
+        // curl -H "Cookie: session=<id>" localhost:5000/me
+        .route("/me", get(me).layer(layer))
+        // curl -i -X POST localhost:5000/login
+        .route("/login", post(login))
+        .with_state(session_store)
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
@@ -0,0 +1,81 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example stateless_session --features="axum"
+//! ```
+//!
+
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use composable_tower_http::{
+    authorize::session::{
+        SameSite, SessionCookie, SessionKey, SessionKeyRing, StatelessSessionAuthorizer,
+        StatelessSessionAuthorizerBuilder,
+    },
+    extension::ExtensionLayerExt,
+    extract::Extracted,
+};
+use http::header::SET_COOKIE;
+use serde::{Deserialize, Serialize};
+
+#[path = "../util/util.rs"]
+mod util;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct User {
+    name: String,
+}
+
+async fn login(State(sessions): State<StatelessSessionAuthorizer<User>>) -> Response {
+    let user = User {
+        name: String::from("user"),
+    };
+
+    match sessions.issue(&user) {
+        Ok(set_cookie) => [(SET_COOKIE, set_cookie)].into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+async fn logout(State(sessions): State<StatelessSessionAuthorizer<User>>) -> impl IntoResponse {
+    [(SET_COOKIE, sessions.clear())]
+}
+
+async fn me(Extracted(user): Extracted<User>) -> impl IntoResponse {
+    Json(user)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    util::init("stateless_session")?;
+
+    // Cookies issued with the previous key stay valid until they expire.
+    let key_ring = SessionKeyRing::new(SessionKey::new("2", [2u8; 32].to_vec()))
+        .previous(SessionKey::new("1", [1u8; 32].to_vec()));
+
+    let sessions = StatelessSessionAuthorizerBuilder::encrypted(key_ring)
+        // Secure cookies are not sent over plain http.
+        .cookie(
+            SessionCookie::new("session")?
+                .secure(false)
+                .same_site(Some(SameSite::Strict)),
+        )
+        .max_age_in_seconds(60 * 60)
+        .build::<User>();
+
+    let app = Router::new()
+        // curl -H "Cookie: session=<value>" localhost:5000/me
+        .route("/me", get(me).layer(sessions.clone().extension_layer()))
+        // curl -i -X POST localhost:5000/login
+        .route("/login", post(login))
+        // curl -i -X POST localhost:5000/logout
+        .route("/logout", post(logout))
+        .with_state(sessions)
+        .layer(util::trace_layer());
+
+    util::serve(app).await
+}
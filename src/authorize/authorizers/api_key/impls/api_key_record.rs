@@ -0,0 +1,67 @@
+use std::{collections::BTreeMap, time::SystemTime};
+
+use crate::authorize::scope::{HasScopes, Scopes};
+
+/// The principal an API key belongs to, extracted by the [`DefaultApiKeyAuthorizer`](super::default_api_key_authorizer::DefaultApiKeyAuthorizer).
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let record = ApiKeyRecord::new("user-1", "ci")
+///     .scope("orders:read")
+///     .expires_at(SystemTime::now() + Duration::from_secs(86_400))
+///     .label("team", "payments");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyRecord {
+    pub owner_id: String,
+    pub name: String,
+    pub scopes: Scopes,
+    pub expires_at: Option<SystemTime>,
+    pub created_at: SystemTime,
+    pub labels: BTreeMap<String, String>,
+}
+
+impl ApiKeyRecord {
+    /// Creates a record without scopes that was created now and never expires.
+    pub fn new(owner_id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            owner_id: owner_id.into(),
+            name: name.into(),
+            scopes: Scopes::default(),
+            expires_at: None,
+            created_at: SystemTime::now(),
+            labels: BTreeMap::new(),
+        }
+    }
+
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.scopes.insert(scope);
+        self
+    }
+
+    pub fn expires_at(mut self, expires_at: SystemTime) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    pub fn created_at(mut self, created_at: SystemTime) -> Self {
+        self.created_at = created_at;
+        self
+    }
+
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+impl HasScopes for ApiKeyRecord {
+    fn scopes(&self) -> &Scopes {
+        &self.scopes
+    }
+}
@@ -0,0 +1,138 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    convert::Infallible,
+    future::Future,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{de, Deserialize, Deserializer};
+
+use crate::authorize::scope::Scopes;
+
+use super::{api_key::ApiKey, api_key_record::ApiKeyRecord};
+
+/// Looks up API keys for the [`DefaultApiKeyAuthorizer`](super::default_api_key_authorizer::DefaultApiKeyAuthorizer).
+///
+/// See [`store`](crate::authorize::store) for stores that can change while the service runs.
+pub trait ApiKeyStore {
+    type Error;
+
+    /// Returns the record of `api_key`, or `None` if it is unknown.
+    ///
+    /// Expired records are returned as well and rejected by the authorizer.
+    fn find_api_key(
+        &self,
+        api_key: &str,
+    ) -> impl Future<Output = Result<Option<ApiKeyRecord>, Self::Error>> + Send;
+}
+
+impl ApiKeyStore for HashMap<ApiKey, ApiKeyRecord> {
+    type Error = Infallible;
+
+    fn find_api_key(
+        &self,
+        api_key: &str,
+    ) -> impl Future<Output = Result<Option<ApiKeyRecord>, Self::Error>> + Send {
+        let found = self
+            .get(&ApiKey::new(Cow::from(api_key.to_string())))
+            .cloned();
+
+        async move { Ok(found) }
+    }
+}
+
+#[derive(Deserialize)]
+struct ApiKeyEntry {
+    key: String,
+    owner_id: String,
+    name: String,
+    #[serde(default)]
+    scopes: Scopes,
+    #[serde(default, deserialize_with = "from_unix_seconds")]
+    expires_at: Option<SystemTime>,
+    #[serde(default, deserialize_with = "from_unix_seconds")]
+    created_at: Option<SystemTime>,
+    #[serde(default)]
+    labels: BTreeMap<String, String>,
+}
+
+impl ApiKeyEntry {
+    fn into_record(self) -> (ApiKey, ApiKeyRecord) {
+        let record = ApiKeyRecord {
+            owner_id: self.owner_id,
+            name: self.name,
+            scopes: self.scopes,
+            expires_at: self.expires_at,
+            created_at: self.created_at.unwrap_or_else(SystemTime::now),
+            labels: self.labels,
+        };
+
+        (ApiKey::new(self.key), record)
+    }
+}
+
+fn from_unix_seconds<'de, D>(deserializer: D) -> Result<Option<SystemTime>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(seconds) = Option::<u64>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+
+    UNIX_EPOCH
+        .checked_add(Duration::from_secs(seconds))
+        .map(Some)
+        .ok_or_else(|| de::Error::custom(format!("timestamp {seconds} is out of range")))
+}
+
+/// Parses a JSON array of API key records.
+///
+/// `scopes`, `expires_at`, `created_at` and `labels` are optional. Timestamps are in seconds since the Unix epoch.
+/// Can be used to load keys from a [`WatchedFile`](crate::authorize::store::WatchedFile).
+///
+/// ```json
+/// [
+///     {
+///         "key": "api-key-1",
+///         "owner_id": "user-1",
+///         "name": "ci",
+///         "scopes": "orders:read orders:write",
+///         "expires_at": 1767225600,
+///         "labels": { "team": "payments" }
+///     }
+/// ]
+/// ```
+pub fn parse_api_keys(contents: &str) -> Result<HashMap<ApiKey, ApiKeyRecord>, serde_json::Error> {
+    let entries: Vec<ApiKeyEntry> = serde_json::from_str(contents)?;
+
+    Ok(entries.into_iter().map(ApiKeyEntry::into_record).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn out_of_range_timestamps_are_rejected() {
+        let api_keys = parse_api_keys(
+            r#"[{ "key": "key-1", "owner_id": "user-1", "name": "ci", "expires_at": 60 }]"#,
+        )
+        .expect("Valid API keys");
+
+        let record = &api_keys[&ApiKey::new("key-1")];
+
+        assert_eq!(
+            record.expires_at,
+            Some(UNIX_EPOCH + Duration::from_secs(60))
+        );
+
+        let err = parse_api_keys(&format!(
+            r#"[{{ "key": "key-1", "owner_id": "user-1", "name": "ci", "expires_at": {} }}]"#,
+            u64::MAX
+        ))
+        .expect_err("Timestamp should be out of range");
+
+        assert!(err.to_string().contains("out of range"));
+    }
+}
@@ -1,17 +1,17 @@
-use std::{borrow::Cow, collections::HashSet, ops::Deref, sync::Arc};
+use std::{collections::HashMap, convert::Infallible, ops::Deref, sync::Arc, time::SystemTime};
 
 use crate::{authorize::header::HeaderExtractor, extract::Extractor};
 
-use super::api_key::ApiKey;
+use super::{api_key::ApiKey, api_key_record::ApiKeyRecord, api_key_store::ApiKeyStore};
 
 #[derive(Debug)]
-pub struct DefaultApiKeyAuthorizerInner<H> {
+pub struct DefaultApiKeyAuthorizerInner<H, S = HashMap<ApiKey, ApiKeyRecord>> {
     header_extractor: H,
-    valid_api_keys: HashSet<ApiKey>,
+    valid_api_keys: S,
 }
 
-impl<H> DefaultApiKeyAuthorizerInner<H> {
-    pub const fn new(header_extractor: H, valid_api_keys: HashSet<ApiKey>) -> Self {
+impl<H, S> DefaultApiKeyAuthorizerInner<H, S> {
+    pub const fn new(header_extractor: H, valid_api_keys: S) -> Self {
         Self {
             header_extractor,
             valid_api_keys,
@@ -19,13 +19,16 @@ impl<H> DefaultApiKeyAuthorizerInner<H> {
     }
 }
 
+/// Authorizes API keys against an [`ApiKeyStore`], e.g. a `HashMap<ApiKey, ApiKeyRecord>`.
+///
+/// Extracts the [`ApiKeyRecord`] of the key and rejects expired keys.
 #[derive(Debug)]
-pub struct DefaultApiKeyAuthorizer<H> {
-    inner: Arc<DefaultApiKeyAuthorizerInner<H>>,
+pub struct DefaultApiKeyAuthorizer<H, S = HashMap<ApiKey, ApiKeyRecord>> {
+    inner: Arc<DefaultApiKeyAuthorizerInner<H, S>>,
 }
 
-impl<H> DefaultApiKeyAuthorizer<H> {
-    pub fn new(header_extractor: H, valid_api_keys: HashSet<ApiKey>) -> Self {
+impl<H, S> DefaultApiKeyAuthorizer<H, S> {
+    pub fn new(header_extractor: H, valid_api_keys: S) -> Self {
         Self {
             inner: Arc::new(DefaultApiKeyAuthorizerInner::new(
                 header_extractor,
@@ -35,7 +38,7 @@ impl<H> DefaultApiKeyAuthorizer<H> {
     }
 }
 
-impl<H> Clone for DefaultApiKeyAuthorizer<H> {
+impl<H, S> Clone for DefaultApiKeyAuthorizer<H, S> {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
@@ -43,44 +46,57 @@ impl<H> Clone for DefaultApiKeyAuthorizer<H> {
     }
 }
 
-impl<H> Deref for DefaultApiKeyAuthorizer<H> {
-    type Target = DefaultApiKeyAuthorizerInner<H>;
+impl<H, S> Deref for DefaultApiKeyAuthorizer<H, S> {
+    type Target = DefaultApiKeyAuthorizerInner<H, S>;
 
     fn deref(&self) -> &Self::Target {
         &self.inner
     }
 }
 
-impl<H> Extractor for DefaultApiKeyAuthorizer<H>
+impl<H, S> Extractor for DefaultApiKeyAuthorizer<H, S>
 where
     H: HeaderExtractor + Send + Sync,
+    S: ApiKeyStore + Send + Sync,
 {
-    type Extracted = ApiKey;
+    type Extracted = ApiKeyRecord;
 
-    type Error = DefaultApiKeyAuthorizeError<H::Error>;
+    type Error = DefaultApiKeyAuthorizeError<H::Error, S::Error>;
 
-    async fn extract(&self, headers: &http::HeaderMap) -> Result<Self::Extracted, Self::Error> {
+    async fn extract(&self, parts: &http::request::Parts) -> Result<Self::Extracted, Self::Error> {
         let api_key_value = self
             .header_extractor
-            .extract_header(headers)
+            .extract_header(&parts.headers)
             .map_err(DefaultApiKeyAuthorizeError::Header)?;
 
-        let used_api_key = ApiKey::new(Cow::from(api_key_value.to_string()));
-
-        if self.valid_api_keys.contains(&used_api_key) {
-            return Ok(used_api_key);
+        let record = self
+            .valid_api_keys
+            .find_api_key(api_key_value)
+            .await
+            .map_err(DefaultApiKeyAuthorizeError::Store)?
+            .ok_or(DefaultApiKeyAuthorizeError::Invalid)?;
+
+        if record.is_expired(SystemTime::now()) {
+            return Err(DefaultApiKeyAuthorizeError::Expired {
+                owner_id: record.owner_id,
+                name: record.name,
+            });
         }
 
-        Err(DefaultApiKeyAuthorizeError::Invalid)
+        Ok(record)
     }
 }
 
 #[derive(Debug, thiserror::Error)]
-pub enum DefaultApiKeyAuthorizeError<H> {
+pub enum DefaultApiKeyAuthorizeError<H, S = Infallible> {
     #[error("Header extraction error: {0}")]
     Header(#[source] H),
+    #[error("API key store error: {0}")]
+    Store(#[source] S),
     #[error("Invalid API key")]
     Invalid,
+    #[error("API key {name} of {owner_id} expired")]
+    Expired { owner_id: String, name: String },
 }
 
 #[cfg(feature = "axum")]
@@ -90,23 +106,95 @@ mod axum {
 
     use super::DefaultApiKeyAuthorizeError;
 
-    impl<H> IntoResponse for DefaultApiKeyAuthorizeError<H>
+    impl<H, S> IntoResponse for DefaultApiKeyAuthorizeError<H, S>
     where
         H: std::error::Error,
+        S: std::error::Error,
     {
         fn into_response(self) -> Response {
+            if let DefaultApiKeyAuthorizeError::Store(_) = self {
+                tracing::error!(err = %self, "Internal server error");
+
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+
             tracing::warn!(err = %self, "Unauthorized");
 
             StatusCode::UNAUTHORIZED.into_response()
         }
     }
 
-    impl<H> From<DefaultApiKeyAuthorizeError<H>> for Response
+    impl<H, S> From<DefaultApiKeyAuthorizeError<H, S>> for Response
     where
         H: std::error::Error,
+        S: std::error::Error,
     {
-        fn from(value: DefaultApiKeyAuthorizeError<H>) -> Self {
+        fn from(value: DefaultApiKeyAuthorizeError<H, S>) -> Self {
             value.into_response()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http::Request;
+
+    use crate::authorize::header::DefaultHeaderExtractor;
+
+    use super::*;
+
+    fn parts(api_key: &str) -> http::request::Parts {
+        Request::builder()
+            .header("x-api-key", api_key)
+            .body(())
+            .expect("Valid request")
+            .into_parts()
+            .0
+    }
+
+    #[tokio::test]
+    async fn records_are_extracted_and_expired_keys_rejected() {
+        let now = SystemTime::now();
+
+        let valid_api_keys = HashMap::from([
+            (
+                ApiKey::new("active"),
+                ApiKeyRecord::new("user-1", "ci")
+                    .scope("orders:read")
+                    .label("team", "payments")
+                    .expires_at(now + Duration::from_secs(60)),
+            ),
+            (
+                ApiKey::new("expired"),
+                ApiKeyRecord::new("user-1", "old").expires_at(now - Duration::from_secs(60)),
+            ),
+        ]);
+
+        let authorizer =
+            DefaultApiKeyAuthorizer::new(DefaultHeaderExtractor::new("x-api-key"), valid_api_keys);
+
+        let record = authorizer
+            .extract(&parts("active"))
+            .await
+            .expect("Key should be valid");
+
+        assert_eq!(record.owner_id, "user-1");
+        assert!(record.scopes.contains("orders:read"));
+        assert_eq!(
+            record.labels.get("team").map(String::as_str),
+            Some("payments")
+        );
+
+        assert!(matches!(
+            authorizer.extract(&parts("expired")).await,
+            Err(DefaultApiKeyAuthorizeError::Expired { name, .. }) if name == "old"
+        ));
+
+        assert!(matches!(
+            authorizer.extract(&parts("unknown")).await,
+            Err(DefaultApiKeyAuthorizeError::Invalid)
+        ));
+    }
+}
@@ -0,0 +1,179 @@
+use std::{collections::HashMap, convert::Infallible};
+
+use subtle::ConstantTimeEq;
+
+use super::{
+    api_key_record::ApiKeyRecord,
+    api_key_store::ApiKeyStore,
+    prefixed_api_key::{validate_prefix, PrefixedApiKey, PrefixedApiKeyError},
+};
+
+#[derive(Debug, Clone)]
+struct HashedApiKey {
+    secret_hash: [u8; 32],
+    record: ApiKeyRecord,
+}
+
+/// [`PrefixedApiKey`]s stored by id, with only a hash of their secret.
+///
+/// Keys with another prefix or an invalid checksum are rejected without a lookup.
+/// Secrets are compared in constant time, also for unknown ids.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let mut store = HashedApiKeyStore::new("acme")?;
+///
+/// let key = store.mint(ApiKeyRecord::new("user-1", "ci"));
+///
+/// let authorizer = DefaultApiKeyAuthorizer::new(header_extractor, store);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HashedApiKeyStore {
+    prefix: String,
+    keys: HashMap<String, HashedApiKey>,
+}
+
+impl HashedApiKeyStore {
+    /// `prefix` must be non-empty and alphanumeric.
+    pub fn new(prefix: impl Into<String>) -> Result<Self, PrefixedApiKeyError> {
+        let prefix = prefix.into();
+
+        validate_prefix(&prefix)?;
+
+        Ok(Self {
+            prefix,
+            keys: HashMap::new(),
+        })
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Generates a key for `record` and stores its hash.
+    ///
+    /// The returned key can not be recovered from the store.
+    pub fn mint(&mut self, record: ApiKeyRecord) -> PrefixedApiKey {
+        let key = PrefixedApiKey::generate(&self.prefix).expect("Prefix was validated");
+
+        self.insert(key.id(), key.secret_hash(), record);
+
+        key
+    }
+
+    /// Stores a key previously minted with [`PrefixedApiKey::generate`] by its id and [`PrefixedApiKey::secret_hash`].
+    pub fn insert(&mut self, id: impl Into<String>, secret_hash: [u8; 32], record: ApiKeyRecord) {
+        self.keys.insert(
+            id.into(),
+            HashedApiKey {
+                secret_hash,
+                record,
+            },
+        );
+    }
+
+    pub fn key(
+        mut self,
+        id: impl Into<String>,
+        secret_hash: [u8; 32],
+        record: ApiKeyRecord,
+    ) -> Self {
+        self.insert(id, secret_hash, record);
+        self
+    }
+
+    /// Revokes the key with the given id.
+    pub fn remove(&mut self, id: &str) -> Option<ApiKeyRecord> {
+        self.keys.remove(id).map(|key| key.record)
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    fn find(&self, api_key: &str) -> Option<ApiKeyRecord> {
+        let key = PrefixedApiKey::parse(api_key).ok()?;
+
+        if key.prefix() != self.prefix {
+            return None;
+        }
+
+        let stored = self.keys.get(key.id());
+
+        // Compare against a dummy hash for unknown ids, so that they take as long as known ones.
+        let secret_hash = stored.map(|stored| stored.secret_hash).unwrap_or_default();
+        let matches = bool::from(key.secret_hash().ct_eq(&secret_hash));
+
+        stored
+            .filter(|_| matches)
+            .map(|stored| stored.record.clone())
+    }
+}
+
+impl ApiKeyStore for HashedApiKeyStore {
+    type Error = Infallible;
+
+    async fn find_api_key(&self, api_key: &str) -> Result<Option<ApiKeyRecord>, Self::Error> {
+        Ok(self.find(api_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn only_minted_keys_are_found() {
+        let mut store = HashedApiKeyStore::new("acme").expect("Prefix should be valid");
+
+        let key = store.mint(ApiKeyRecord::new("user-1", "ci"));
+
+        let record = store
+            .find_api_key(&key.to_string())
+            .await
+            .unwrap()
+            .expect("Key should be found");
+
+        assert_eq!(record.name, "ci");
+
+        let other = PrefixedApiKey::generate("acme").expect("Prefix should be valid");
+        store.insert(
+            other.id(),
+            key.secret_hash(),
+            ApiKeyRecord::new("user-2", "ci"),
+        );
+
+        assert!(store
+            .find_api_key(&other.to_string())
+            .await
+            .unwrap()
+            .is_none());
+
+        let other_prefix = HashedApiKeyStore::new("other")
+            .expect("Prefix should be valid")
+            .key(
+                key.id(),
+                key.secret_hash(),
+                ApiKeyRecord::new("user-1", "ci"),
+            );
+
+        assert!(other_prefix
+            .find_api_key(&key.to_string())
+            .await
+            .unwrap()
+            .is_none());
+
+        store.remove(key.id());
+
+        assert!(store
+            .find_api_key(&key.to_string())
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -1,4 +1,9 @@
 mod api_key;
+mod api_key_record;
+pub mod api_key_store;
 pub mod default_api_key_authorizer;
+pub mod hashed_api_key_store;
+pub mod prefixed_api_key;
 
 pub use api_key::ApiKey;
+pub use api_key_record::ApiKeyRecord;
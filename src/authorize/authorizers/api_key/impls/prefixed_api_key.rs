@@ -0,0 +1,209 @@
+use std::fmt;
+
+use rand::{rngs::OsRng, Rng};
+use sha2::{Digest, Sha256};
+
+const BASE62: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+const ID_LENGTH: usize = 12;
+const SECRET_LENGTH: usize = 32;
+/// `62^6` is above `u32::MAX`.
+const CHECKSUM_LENGTH: usize = 6;
+
+/// An API key of the form `<prefix>_<id>_<secret><checksum>`.
+///
+/// - The prefix identifies the issuer, so leaked-key scanners can recognize the keys.
+/// - The id is public and used to look up the key.
+/// - The secret is only stored as a SHA-256 hash. It is random, so a fast hash suffices.
+/// - The checksum is a base62 CRC32 of everything before it, so typos and random strings are rejected without a lookup.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let key = PrefixedApiKey::generate("acme")?;
+///
+/// // Show this to the user once.
+/// let value = key.to_string();
+///
+/// // Store only these.
+/// let (id, secret_hash) = (key.id(), key.secret_hash());
+/// ```
+///
+/// Keys are deliberately not comparable, compare [`PrefixedApiKey::secret_hash`]es in constant time instead.
+#[derive(Clone)]
+pub struct PrefixedApiKey {
+    prefix: String,
+    id: String,
+    secret: String,
+}
+
+impl fmt::Debug for PrefixedApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrefixedApiKey")
+            .field("prefix", &self.prefix)
+            .field("id", &self.id)
+            .field("secret", &"...")
+            .finish()
+    }
+}
+
+impl PrefixedApiKey {
+    /// Mints a new key with a random id and secret.
+    ///
+    /// `prefix` must be non-empty and alphanumeric.
+    pub fn generate(prefix: &str) -> Result<Self, PrefixedApiKeyError> {
+        validate_prefix(prefix)?;
+
+        Ok(Self {
+            prefix: prefix.to_string(),
+            id: random_base62(ID_LENGTH),
+            secret: random_base62(SECRET_LENGTH),
+        })
+    }
+
+    /// Parses a key and verifies its checksum.
+    pub fn parse(value: &str) -> Result<Self, PrefixedApiKeyError> {
+        let mut parts = value.splitn(3, '_');
+
+        let (Some(prefix), Some(id), Some(tail)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(PrefixedApiKeyError::Format);
+        };
+
+        validate_prefix(prefix)?;
+
+        if id.len() != ID_LENGTH
+            || tail.len() != SECRET_LENGTH + CHECKSUM_LENGTH
+            || !is_base62(id)
+            || !is_base62(tail)
+        {
+            return Err(PrefixedApiKeyError::Format);
+        }
+
+        let (secret, checksum) = tail.split_at(SECRET_LENGTH);
+        let (checksummed, _) = value.split_at(value.len() - CHECKSUM_LENGTH);
+
+        if checksum != encode_checksum(crc32(checksummed.as_bytes())) {
+            return Err(PrefixedApiKeyError::Checksum);
+        }
+
+        Ok(Self {
+            prefix: prefix.to_string(),
+            id: id.to_string(),
+            secret: secret.to_string(),
+        })
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The SHA-256 hash of the secret, which is all that needs to be stored.
+    pub fn secret_hash(&self) -> [u8; 32] {
+        Sha256::digest(self.secret.as_bytes()).into()
+    }
+}
+
+impl fmt::Display for PrefixedApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = format!("{}_{}_{}", self.prefix, self.id, self.secret);
+        let checksum = encode_checksum(crc32(value.as_bytes()));
+
+        write!(f, "{value}{checksum}")
+    }
+}
+
+pub(super) fn validate_prefix(prefix: &str) -> Result<(), PrefixedApiKeyError> {
+    if prefix.is_empty() || !prefix.bytes().all(|byte| byte.is_ascii_alphanumeric()) {
+        return Err(PrefixedApiKeyError::Prefix);
+    }
+
+    Ok(())
+}
+
+fn is_base62(value: &str) -> bool {
+    value.bytes().all(|byte| byte.is_ascii_alphanumeric())
+}
+
+fn random_base62(length: usize) -> String {
+    (0..length)
+        .map(|_| BASE62[OsRng.gen_range(0..BASE62.len())] as char)
+        .collect()
+}
+
+fn encode_checksum(mut checksum: u32) -> String {
+    let mut encoded = [b'0'; CHECKSUM_LENGTH];
+
+    for digit in encoded.iter_mut().rev() {
+        *digit = BASE62[(checksum % 62) as usize];
+        checksum /= 62;
+    }
+
+    encoded.iter().map(|&digit| digit as char).collect()
+}
+
+/// CRC-32/ISO-HDLC, as used by zlib and GitHub tokens.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+
+    for &byte in bytes {
+        crc ^= byte as u32;
+
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+
+    !crc
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PrefixedApiKeyError {
+    #[error("Prefix must be non-empty and alphanumeric")]
+    Prefix,
+    #[error("Malformed API key")]
+    Format,
+    #[error("Invalid API key checksum")]
+    Checksum,
+}
+
+#[cfg(test)]
+mod tests {
+    use subtle::ConstantTimeEq;
+
+    use super::*;
+
+    #[test]
+    fn generated_keys_round_trip_and_typos_are_rejected() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let key = PrefixedApiKey::generate("acme").expect("Prefix should be valid");
+        let value = key.to_string();
+
+        assert!(value.starts_with("acme_"));
+        let parsed = PrefixedApiKey::parse(&value).expect("Key should be valid");
+
+        assert_eq!(parsed.id(), key.id());
+        assert!(bool::from(parsed.secret_hash().ct_eq(&key.secret_hash())));
+
+        let mut typo = value.into_bytes();
+        let index = typo.len() - CHECKSUM_LENGTH - 1;
+        typo[index] = if typo[index] == b'a' { b'b' } else { b'a' };
+        let typo = String::from_utf8(typo).expect("Valid UTF-8");
+
+        assert!(matches!(
+            PrefixedApiKey::parse(&typo),
+            Err(PrefixedApiKeyError::Checksum)
+        ));
+
+        assert!(matches!(
+            PrefixedApiKey::generate("ac_me"),
+            Err(PrefixedApiKeyError::Prefix)
+        ));
+    }
+}
@@ -1,6 +1,9 @@
 mod impls;
 
 pub use impls::{
+    api_key_store::{parse_api_keys, ApiKeyStore},
     default_api_key_authorizer::{DefaultApiKeyAuthorizeError, DefaultApiKeyAuthorizer},
-    ApiKey,
+    hashed_api_key_store::HashedApiKeyStore,
+    prefixed_api_key::{PrefixedApiKey, PrefixedApiKeyError},
+    ApiKey, ApiKeyRecord,
 };
@@ -0,0 +1,161 @@
+use std::{collections::HashMap, collections::HashSet, convert::Infallible, path::Path};
+
+use subtle::ConstantTimeEq;
+
+use super::{
+    basic_auth_user::BasicAuthUser,
+    password_hash::{PasswordHash, PasswordHashError},
+    user_store::UserStore,
+};
+
+/// Verifies basic auth credentials for the [`DefaultBasicAuthAuthorizer`](super::default_basic_auth_authorizer::DefaultBasicAuthAuthorizer).
+///
+/// Verification runs on the executor, so it must be cheap. Stores with password hashes implement [`UserStore`] instead.
+pub trait CredentialStore {
+    fn verify(&self, username: &str, password: &str) -> bool;
+}
+
+/// Compares plaintext credentials in constant time.
+impl CredentialStore for HashSet<BasicAuthUser> {
+    fn verify(&self, username: &str, password: &str) -> bool {
+        self.iter().fold(false, |found, user| {
+            let matches = user.username.as_bytes().ct_eq(username.as_bytes())
+                & user.password.as_bytes().ct_eq(password.as_bytes());
+
+            found | bool::from(matches)
+        })
+    }
+}
+
+/// Password hashes keyed by username.
+///
+/// Hashes are verified on the blocking thread pool of tokio.
+#[derive(Debug, Clone, Default)]
+pub struct HashedCredentialStore {
+    users: HashMap<String, PasswordHash>,
+    /// Verified for unknown users, so that they take as long as known ones.
+    dummy: Option<PasswordHash>,
+}
+
+impl HashedCredentialStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn user(mut self, username: impl Into<String>, password_hash: PasswordHash) -> Self {
+        self.insert(username, password_hash);
+        self
+    }
+
+    pub fn insert(&mut self, username: impl Into<String>, password_hash: PasswordHash) {
+        if self.dummy.is_none() {
+            self.dummy = Some(password_hash.clone());
+        }
+
+        self.users.insert(username.into(), password_hash);
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+
+    /// Parses an htpasswd file with one `username:hash` per line.
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    /// Only bcrypt (`htpasswd -B`), Argon2id and PBKDF2 hashes are supported.
+    pub fn from_htpasswd(htpasswd: &str) -> Result<Self, HtpasswdError> {
+        let mut store = Self::new();
+
+        for (index, entry) in htpasswd.lines().enumerate() {
+            let entry = entry.trim();
+
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+
+            let line = index + 1;
+
+            let (username, hash) = entry
+                .split_once(':')
+                .ok_or(HtpasswdError::Format { line })?;
+
+            let hash = PasswordHash::parse(hash).map_err(|source| HtpasswdError::Hash {
+                line,
+                username: username.to_string(),
+                source,
+            })?;
+
+            store.insert(username, hash);
+        }
+
+        Ok(store)
+    }
+
+    pub async fn from_htpasswd_file(path: impl AsRef<Path>) -> Result<Self, HtpasswdError> {
+        let htpasswd = tokio::fs::read_to_string(path)
+            .await
+            .map_err(HtpasswdError::Read)?;
+
+        Self::from_htpasswd(&htpasswd)
+    }
+}
+
+impl UserStore for HashedCredentialStore {
+    type Error = Infallible;
+
+    async fn verify_user(&self, username: &str, password: &str) -> Result<bool, Self::Error> {
+        match self.users.get(username) {
+            Some(hash) => Ok(hash.spawn_verify(password).await),
+            None => {
+                if let Some(dummy) = &self.dummy {
+                    dummy.spawn_verify(password).await;
+                }
+
+                Ok(false)
+            }
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum HtpasswdError {
+    #[error("Failed to read htpasswd file: {0}")]
+    Read(#[source] std::io::Error),
+    #[error("Line {line} is not in the form: `username:hash`")]
+    Format { line: usize },
+    #[error("Invalid hash for user {username} on line {line}: {source}")]
+    Hash {
+        line: usize,
+        username: String,
+        #[source]
+        source: PasswordHashError,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn htpasswd_files_are_loaded() {
+        let hash = PasswordHash::bcrypt("password", 4).expect("Failed to hash");
+
+        let htpasswd = format!("# users\n\nuser:{}\n", hash.as_str());
+
+        let store = HashedCredentialStore::from_htpasswd(&htpasswd).expect("Valid htpasswd");
+
+        assert_eq!(store.len(), 1);
+        assert!(store.verify_user("user", "password").await.unwrap());
+        assert!(!store.verify_user("user", "wrong").await.unwrap());
+        assert!(!store.verify_user("unknown", "password").await.unwrap());
+
+        let err = HashedCredentialStore::from_htpasswd("user:{SHA}W6ph5Mm5Pz8GgiULbPgzG37mj9g=")
+            .expect_err("SHA1 is not supported");
+
+        assert!(matches!(err, HtpasswdError::Hash { line: 1, .. }));
+    }
+}
@@ -1,17 +1,17 @@
-use std::{collections::HashSet, ops::Deref, sync::Arc};
+use std::{collections::HashSet, convert::Infallible, ops::Deref, sync::Arc};
 
 use crate::{authorize::header::basic_auth::BasicAuthExtractor, extract::Extractor};
 
-use super::basic_auth_user::BasicAuthUser;
+use super::{basic_auth_user::BasicAuthUser, user_store::UserStore};
 
 #[derive(Debug)]
-pub struct DefaultBasicAuthAuthorizerInner<Ba> {
+pub struct DefaultBasicAuthAuthorizerInner<Ba, C = HashSet<BasicAuthUser>> {
     basic_auth_extractor: Ba,
-    users: HashSet<BasicAuthUser>,
+    users: C,
 }
 
-impl<Ba> DefaultBasicAuthAuthorizerInner<Ba> {
-    pub const fn new(basic_auth_extractor: Ba, users: HashSet<BasicAuthUser>) -> Self {
+impl<Ba, C> DefaultBasicAuthAuthorizerInner<Ba, C> {
+    pub const fn new(basic_auth_extractor: Ba, users: C) -> Self {
         Self {
             basic_auth_extractor,
             users,
@@ -19,13 +19,15 @@ impl<Ba> DefaultBasicAuthAuthorizerInner<Ba> {
     }
 }
 
+/// Authorizes basic auth users against a [`UserStore`],
+/// e.g. a `HashSet<BasicAuthUser>` or a [`HashedCredentialStore`](super::credential_store::HashedCredentialStore).
 #[derive(Debug)]
-pub struct DefaultBasicAuthAuthorizer<Ba> {
-    inner: Arc<DefaultBasicAuthAuthorizerInner<Ba>>,
+pub struct DefaultBasicAuthAuthorizer<Ba, C = HashSet<BasicAuthUser>> {
+    inner: Arc<DefaultBasicAuthAuthorizerInner<Ba, C>>,
 }
 
-impl<Ba> DefaultBasicAuthAuthorizer<Ba> {
-    pub fn new(basic_auth_extractor: Ba, users: HashSet<BasicAuthUser>) -> Self {
+impl<Ba, C> DefaultBasicAuthAuthorizer<Ba, C> {
+    pub fn new(basic_auth_extractor: Ba, users: C) -> Self {
         Self {
             inner: Arc::new(DefaultBasicAuthAuthorizerInner::new(
                 basic_auth_extractor,
@@ -35,7 +37,7 @@ impl<Ba> DefaultBasicAuthAuthorizer<Ba> {
     }
 }
 
-impl<Ba> Clone for DefaultBasicAuthAuthorizer<Ba> {
+impl<Ba, C> Clone for DefaultBasicAuthAuthorizer<Ba, C> {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
@@ -43,32 +45,37 @@ impl<Ba> Clone for DefaultBasicAuthAuthorizer<Ba> {
     }
 }
 
-impl<Ba> Deref for DefaultBasicAuthAuthorizer<Ba> {
-    type Target = DefaultBasicAuthAuthorizerInner<Ba>;
+impl<Ba, C> Deref for DefaultBasicAuthAuthorizer<Ba, C> {
+    type Target = DefaultBasicAuthAuthorizerInner<Ba, C>;
 
     fn deref(&self) -> &Self::Target {
         &self.inner
     }
 }
 
-impl<Ba> Extractor for DefaultBasicAuthAuthorizer<Ba>
+impl<Ba, C> Extractor for DefaultBasicAuthAuthorizer<Ba, C>
 where
     Ba: BasicAuthExtractor + Send + Sync,
+    C: UserStore + Send + Sync,
 {
     type Extracted = BasicAuthUser;
 
-    type Error = DefaultBasicAuthAuthorizeError<Ba::Error>;
+    type Error = DefaultBasicAuthAuthorizeError<Ba::Error, C::Error>;
 
-    async fn extract(&self, headers: &http::HeaderMap) -> Result<Self::Extracted, Self::Error> {
-        let used_basic_auth = self
+    async fn extract(&self, parts: &http::request::Parts) -> Result<Self::Extracted, Self::Error> {
+        let (username, password) = self
             .basic_auth_extractor
-            .extract_basic_auth(headers)
+            .extract_basic_auth(&parts.headers)
             .map_err(DefaultBasicAuthAuthorizeError::BasicAuth)?;
 
-        let basic_auth_user: BasicAuthUser = used_basic_auth.into();
+        let verified = self
+            .users
+            .verify_user(&username, &password)
+            .await
+            .map_err(DefaultBasicAuthAuthorizeError::Store)?;
 
-        if self.users.contains(&basic_auth_user) {
-            return Ok(basic_auth_user);
+        if verified {
+            return Ok(BasicAuthUser::new(username, password));
         }
 
         Err(DefaultBasicAuthAuthorizeError::Invalid)
@@ -76,9 +83,11 @@ where
 }
 
 #[derive(Debug, thiserror::Error)]
-pub enum DefaultBasicAuthAuthorizeError<Ba> {
+pub enum DefaultBasicAuthAuthorizeError<Ba, S = Infallible> {
     #[error("Basic auth extraction error: {0}")]
     BasicAuth(#[source] Ba),
+    #[error("User store error: {0}")]
+    Store(#[source] S),
     #[error("Invalid basic auth")]
     Invalid,
 }
@@ -90,11 +99,18 @@ mod axum {
 
     use super::DefaultBasicAuthAuthorizeError;
 
-    impl<Ba> IntoResponse for DefaultBasicAuthAuthorizeError<Ba>
+    impl<Ba, S> IntoResponse for DefaultBasicAuthAuthorizeError<Ba, S>
     where
         Ba: std::error::Error,
+        S: std::error::Error,
     {
         fn into_response(self) -> Response {
+            if let DefaultBasicAuthAuthorizeError::Store(_) = self {
+                tracing::error!(err = %self, "Internal server error");
+
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+
             tracing::warn!(err = %self, "Unauthorized");
 
             (
@@ -108,11 +124,12 @@ mod axum {
         }
     }
 
-    impl<Ba> From<DefaultBasicAuthAuthorizeError<Ba>> for Response
+    impl<Ba, S> From<DefaultBasicAuthAuthorizeError<Ba, S>> for Response
     where
         Ba: std::error::Error,
+        S: std::error::Error,
     {
-        fn from(value: DefaultBasicAuthAuthorizeError<Ba>) -> Self {
+        fn from(value: DefaultBasicAuthAuthorizeError<Ba, S>) -> Self {
             value.into_response()
         }
     }
@@ -1,4 +1,7 @@
 mod basic_auth_user;
+pub mod credential_store;
 pub mod default_basic_auth_authorizer;
+pub mod password_hash;
+pub mod user_store;
 
 pub use basic_auth_user::BasicAuthUser;
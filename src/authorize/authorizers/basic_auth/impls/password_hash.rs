@@ -0,0 +1,182 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use pbkdf2::Pbkdf2;
+
+/// A password hash in one of the supported formats.
+///
+/// Verification is constant-time with respect to the hash.
+#[derive(Clone, PartialEq, Eq)]
+pub enum PasswordHash {
+    /// A PHC string starting with `$argon2id$`.
+    Argon2id(String),
+    /// A modular crypt string starting with `$2a$`, `$2b$` or `$2y$`, as written by `htpasswd -B`.
+    Bcrypt(String),
+    /// A PHC string starting with `$pbkdf2-sha256$` or `$pbkdf2-sha512$`.
+    Pbkdf2(String),
+}
+
+impl core::fmt::Debug for PasswordHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            PasswordHash::Argon2id(_) => "Argon2id",
+            PasswordHash::Bcrypt(_) => "Bcrypt",
+            PasswordHash::Pbkdf2(_) => "Pbkdf2",
+        };
+
+        f.debug_tuple(kind).field(&"...").finish()
+    }
+}
+
+impl PasswordHash {
+    /// Detects the format of `hash` by its prefix and validates it.
+    pub fn parse(hash: &str) -> Result<Self, PasswordHashError> {
+        if hash.starts_with("$argon2id$") {
+            argon2::PasswordHash::new(hash).map_err(PasswordHashError::Phc)?;
+
+            return Ok(PasswordHash::Argon2id(hash.to_string()));
+        }
+
+        if ["$2a$", "$2b$", "$2y$"]
+            .iter()
+            .any(|prefix| hash.starts_with(prefix))
+        {
+            hash.parse::<bcrypt::HashParts>()
+                .map_err(PasswordHashError::Bcrypt)?;
+
+            return Ok(PasswordHash::Bcrypt(hash.to_string()));
+        }
+
+        if hash.starts_with("$pbkdf2-sha256$") || hash.starts_with("$pbkdf2-sha512$") {
+            argon2::PasswordHash::new(hash).map_err(PasswordHashError::Phc)?;
+
+            return Ok(PasswordHash::Pbkdf2(hash.to_string()));
+        }
+
+        Err(PasswordHashError::Unsupported)
+    }
+
+    /// Hashes `password` with Argon2id and the default parameters.
+    pub fn argon2id(password: &str) -> Result<Self, PasswordHashError> {
+        let salt = SaltString::generate(&mut OsRng);
+
+        let hash = Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(PasswordHashError::Phc)?;
+
+        Ok(PasswordHash::Argon2id(hash.to_string()))
+    }
+
+    /// Hashes `password` with bcrypt and the given cost.
+    pub fn bcrypt(password: &str, cost: u32) -> Result<Self, PasswordHashError> {
+        let hash = bcrypt::hash(password, cost).map_err(PasswordHashError::Bcrypt)?;
+
+        Ok(PasswordHash::Bcrypt(hash))
+    }
+
+    /// Hashes `password` with PBKDF2-HMAC-SHA256 and the default parameters.
+    pub fn pbkdf2(password: &str) -> Result<Self, PasswordHashError> {
+        let salt = SaltString::generate(&mut OsRng);
+
+        let hash = Pbkdf2
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(PasswordHashError::Phc)?;
+
+        Ok(PasswordHash::Pbkdf2(hash.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            PasswordHash::Argon2id(hash)
+            | PasswordHash::Bcrypt(hash)
+            | PasswordHash::Pbkdf2(hash) => hash,
+        }
+    }
+
+    /// Returns `true` if `password` matches the hash.
+    ///
+    /// This is deliberately slow. Use [`PasswordHash::spawn_verify`] in async code.
+    pub fn verify(&self, password: &str) -> bool {
+        match self {
+            PasswordHash::Argon2id(hash) => argon2::PasswordHash::new(hash).is_ok_and(|hash| {
+                Argon2::default()
+                    .verify_password(password.as_bytes(), &hash)
+                    .is_ok()
+            }),
+            PasswordHash::Bcrypt(hash) => bcrypt::verify(password, hash).unwrap_or(false),
+            PasswordHash::Pbkdf2(hash) => argon2::PasswordHash::new(hash)
+                .is_ok_and(|hash| Pbkdf2.verify_password(password.as_bytes(), &hash).is_ok()),
+        }
+    }
+
+    /// Like [`PasswordHash::verify`], but runs on the blocking thread pool of tokio, so that it does not stall other requests.
+    pub async fn spawn_verify(&self, password: &str) -> bool {
+        let hash = self.clone();
+        let password = password.to_string();
+
+        tokio::task::spawn_blocking(move || hash.verify(&password))
+            .await
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PasswordHashError {
+    #[error("Unsupported password hash format")]
+    Unsupported,
+    #[error("Invalid password hash: {0}")]
+    Phc(#[source] argon2::password_hash::Error),
+    #[error("Invalid bcrypt hash: {0}")]
+    Bcrypt(#[source] bcrypt::BcryptError),
+}
+
+#[cfg(test)]
+mod tests {
+    use argon2::{Algorithm, Params, Version};
+
+    use super::*;
+
+    #[test]
+    fn hashes_are_verified() {
+        // The default parameters are too slow for unoptimized builds.
+        let salt = SaltString::generate(&mut OsRng);
+
+        let argon2id = Argon2::new(
+            Algorithm::Argon2id,
+            Version::V0x13,
+            Params::new(1024, 1, 1, None).expect("Valid params"),
+        )
+        .hash_password(b"password", &salt)
+        .expect("Failed to hash")
+        .to_string();
+
+        let pbkdf2 = Pbkdf2
+            .hash_password_customized(
+                b"password",
+                None,
+                None,
+                pbkdf2::Params {
+                    rounds: 1000,
+                    output_length: 32,
+                },
+                &salt,
+            )
+            .expect("Failed to hash")
+            .to_string();
+
+        let bcrypt = PasswordHash::bcrypt("password", 4).expect("Failed to hash");
+
+        for hash in [argon2id.as_str(), pbkdf2.as_str(), bcrypt.as_str()] {
+            let hash = PasswordHash::parse(hash).expect("Hash should be parsable");
+
+            assert!(hash.verify("password"));
+            assert!(!hash.verify("wrong"));
+        }
+
+        assert!(matches!(
+            PasswordHash::parse("$apr1$salt$hash"),
+            Err(PasswordHashError::Unsupported)
+        ));
+    }
+}
@@ -0,0 +1,46 @@
+use std::{convert::Infallible, future::Future};
+
+use super::{credential_store::CredentialStore, password_hash::PasswordHash};
+
+/// Verifies basic auth credentials for the [`DefaultBasicAuthAuthorizer`](super::default_basic_auth_authorizer::DefaultBasicAuthAuthorizer).
+///
+/// Every [`CredentialStore`] is a `UserStore`.
+/// See [`store`](crate::authorize::store) for stores that can change while the service runs.
+pub trait UserStore {
+    type Error;
+
+    fn verify_user(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+}
+
+impl<C> UserStore for C
+where
+    C: CredentialStore,
+{
+    type Error = Infallible;
+
+    fn verify_user(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send {
+        let verified = self.verify(username, password);
+
+        async move { Ok(verified) }
+    }
+}
+
+/// Looks up password hashes by username, e.g. in a database.
+///
+/// Wrap it in a [`CachedStore`](crate::authorize::store::CachedStore) to use it as a [`UserStore`].
+pub trait UserLookup {
+    type Error;
+
+    fn find_password_hash(
+        &self,
+        username: &str,
+    ) -> impl Future<Output = Result<Option<PasswordHash>, Self::Error>> + Send;
+}
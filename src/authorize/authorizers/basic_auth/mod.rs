@@ -1,6 +1,9 @@
 mod impls;
 
 pub use impls::{
+    credential_store::{CredentialStore, HashedCredentialStore, HtpasswdError},
     default_basic_auth_authorizer::{DefaultBasicAuthAuthorizeError, DefaultBasicAuthAuthorizer},
+    password_hash::{PasswordHash, PasswordHashError},
+    user_store::{UserLookup, UserStore},
     BasicAuthUser,
 };
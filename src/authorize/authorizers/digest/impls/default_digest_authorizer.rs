@@ -0,0 +1,539 @@
+use std::{borrow::Cow, collections::HashMap, ops::Deref, sync::Arc};
+
+use http::{request::Parts, HeaderValue};
+use rand::{rngs::OsRng, RngCore};
+use subtle::ConstantTimeEq;
+
+use crate::{
+    authorize::{
+        digest::nonce::{NonceStatus, NonceStore},
+        header::digest::{DigestAlgorithm, DigestCredentials, DigestExtractor},
+    },
+    extract::Extractor,
+};
+
+use super::digest_user::DigestUser;
+
+/// The parameters of a `WWW-Authenticate: Digest ...` challenge.
+#[derive(Debug, Clone)]
+pub struct DigestChallenge {
+    pub realm: String,
+    pub nonce: String,
+    pub opaque: String,
+    pub algorithms: Vec<DigestAlgorithm>,
+    /// The client used an expired nonce and may retry with the new one without asking the user.
+    pub stale: bool,
+}
+
+impl DigestChallenge {
+    /// One `WWW-Authenticate` header value per algorithm, in order of preference.
+    pub fn header_values(&self) -> Vec<HeaderValue> {
+        self.algorithms
+            .iter()
+            .filter_map(|algorithm| {
+                let mut challenge = format!(
+                    "Digest realm=\"{}\", qop=\"auth\", algorithm={}, nonce=\"{}\", opaque=\"{}\"",
+                    quote(&self.realm),
+                    algorithm.as_str(),
+                    self.nonce,
+                    self.opaque,
+                );
+
+                if self.stale {
+                    challenge.push_str(", stale=true");
+                }
+
+                HeaderValue::try_from(challenge).ok()
+            })
+            .collect()
+    }
+}
+
+fn quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[derive(Debug)]
+pub struct DefaultDigestAuthorizerInner<D, N> {
+    digest_extractor: D,
+    nonce_store: N,
+    realm: Cow<'static, str>,
+    opaque: String,
+    algorithms: Vec<DigestAlgorithm>,
+    users: HashMap<Cow<'static, str>, DigestUser>,
+}
+
+impl<D, N> DefaultDigestAuthorizerInner<D, N> {
+    pub fn realm(&self) -> &str {
+        &self.realm
+    }
+
+    /// Checks everything but the nonce count.
+    fn verify(
+        &self,
+        credentials: &DigestCredentials,
+        parts: &Parts,
+    ) -> Result<(&DigestUser, u64), DigestFailure> {
+        if !self.algorithms.contains(&credentials.algorithm) {
+            return Err(DigestFailure::Algorithm);
+        }
+
+        if credentials.realm != self.realm {
+            return Err(DigestFailure::Realm);
+        }
+
+        if credentials.opaque.as_deref() != Some(self.opaque.as_str()) {
+            return Err(DigestFailure::Opaque);
+        }
+
+        if credentials.qop.as_deref() != Some("auth") {
+            return Err(DigestFailure::Qop);
+        }
+
+        let request_uri = parts
+            .uri
+            .path_and_query()
+            .map(|path_and_query| path_and_query.as_str())
+            .unwrap_or("/");
+
+        if credentials.uri != request_uri && credentials.uri != parts.uri.to_string() {
+            return Err(DigestFailure::Uri);
+        }
+
+        let (Some(nc), Some(cnonce)) = (&credentials.nc, &credentials.cnonce) else {
+            return Err(DigestFailure::Qop);
+        };
+
+        let nc_value = u64::from_str_radix(nc, 16).map_err(|_| DigestFailure::NonceCount)?;
+
+        let user = self.users.get(credentials.username.as_str());
+
+        // Hash an empty password for unknown users, so that they take as long as known ones.
+        let password = user.map(|user| user.password.as_ref()).unwrap_or_default();
+
+        let algorithm = credentials.algorithm;
+
+        let ha1 = algorithm.hash(&[&credentials.username, &self.realm, password]);
+        let ha2 = algorithm.hash(&[parts.method.as_str(), &credentials.uri]);
+
+        let expected = algorithm.hash(&[&ha1, &credentials.nonce, nc, cnonce, "auth", &ha2]);
+
+        let response = credentials.response.to_ascii_lowercase();
+
+        let matches = bool::from(expected.as_bytes().ct_eq(response.as_bytes()));
+
+        let user = user.ok_or(DigestFailure::UnknownUser)?;
+
+        if !matches {
+            return Err(DigestFailure::Response);
+        }
+
+        Ok((user, nc_value))
+    }
+}
+
+impl<D, N> DefaultDigestAuthorizerInner<D, N>
+where
+    N: NonceStore,
+{
+    async fn challenge(&self, stale: bool) -> Result<DigestChallenge, N::Error> {
+        Ok(DigestChallenge {
+            realm: self.realm.to_string(),
+            nonce: self.nonce_store.issue().await?,
+            opaque: self.opaque.clone(),
+            algorithms: self.algorithms.clone(),
+            stale,
+        })
+    }
+}
+
+/// Authorizes requests with HTTP Digest authentication (RFC 7616), using `qop=auth`.
+///
+/// Rejected requests carry a fresh challenge, so that the first unauthenticated request
+/// receives the nonce the client needs.
+#[derive(Debug)]
+pub struct DefaultDigestAuthorizer<D, N> {
+    inner: Arc<DefaultDigestAuthorizerInner<D, N>>,
+}
+
+impl<D, N> Clone for DefaultDigestAuthorizer<D, N> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<D, N> Deref for DefaultDigestAuthorizer<D, N> {
+    type Target = DefaultDigestAuthorizerInner<D, N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<D, N> Extractor for DefaultDigestAuthorizer<D, N>
+where
+    D: DigestExtractor + Send + Sync,
+    D::Error: Send,
+    N: NonceStore + Send + Sync,
+{
+    type Extracted = DigestUser;
+
+    type Error = DefaultDigestAuthorizeError<D::Error, N::Error>;
+
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+        let credentials = match self.digest_extractor.extract_digest(&parts.headers) {
+            Ok(credentials) => credentials,
+            Err(source) => {
+                let challenge = self
+                    .challenge(false)
+                    .await
+                    .map_err(DefaultDigestAuthorizeError::Nonce)?;
+
+                return Err(DefaultDigestAuthorizeError::Digest { source, challenge });
+            }
+        };
+
+        let failure = match self.verify(&credentials, parts) {
+            Ok((user, nc)) => {
+                let status = self
+                    .nonce_store
+                    .consume(&credentials.nonce, nc)
+                    .await
+                    .map_err(DefaultDigestAuthorizeError::Nonce)?;
+
+                match status {
+                    NonceStatus::Valid => return Ok(user.clone()),
+                    NonceStatus::Unknown => DigestFailure::UnknownNonce,
+                    NonceStatus::Stale => DigestFailure::StaleNonce,
+                    NonceStatus::Replayed => DigestFailure::Replayed,
+                }
+            }
+            Err(failure) => failure,
+        };
+
+        let challenge = self
+            .challenge(failure == DigestFailure::StaleNonce)
+            .await
+            .map_err(DefaultDigestAuthorizeError::Nonce)?;
+
+        Err(DefaultDigestAuthorizeError::Invalid {
+            reason: failure,
+            challenge,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum DigestFailure {
+    #[error("Algorithm not allowed")]
+    Algorithm,
+    #[error("Realm does not match")]
+    Realm,
+    #[error("Opaque does not match")]
+    Opaque,
+    #[error("Quality of protection is not auth")]
+    Qop,
+    #[error("URI does not match the request")]
+    Uri,
+    #[error("Malformed nonce count")]
+    NonceCount,
+    #[error("Unknown user")]
+    UnknownUser,
+    #[error("Invalid response")]
+    Response,
+    #[error("Unknown nonce")]
+    UnknownNonce,
+    #[error("Stale nonce")]
+    StaleNonce,
+    #[error("Nonce count replayed")]
+    Replayed,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DefaultDigestAuthorizeError<D, N> {
+    #[error("Digest extraction error: {source}")]
+    Digest {
+        #[source]
+        source: D,
+        challenge: DigestChallenge,
+    },
+    #[error("Invalid digest: {reason}")]
+    Invalid {
+        reason: DigestFailure,
+        challenge: DigestChallenge,
+    },
+    #[error("Nonce store error: {0}")]
+    Nonce(#[source] N),
+}
+
+impl<D, N> DefaultDigestAuthorizeError<D, N> {
+    pub fn challenge(&self) -> Option<&DigestChallenge> {
+        match self {
+            DefaultDigestAuthorizeError::Digest { challenge, .. }
+            | DefaultDigestAuthorizeError::Invalid { challenge, .. } => Some(challenge),
+            DefaultDigestAuthorizeError::Nonce(_) => None,
+        }
+    }
+}
+
+/// Builds a [`DefaultDigestAuthorizer`].
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let digest_authorizer = DefaultDigestAuthorizerBuilder::new(
+///     DefaultDigestExtractor::new(),
+///     InMemoryNonceStore::new(300, NonZeroUsize::new(10_000).unwrap()),
+///     "devices@example.com",
+/// )
+/// .algorithms(&[DigestAlgorithm::Sha256, DigestAlgorithm::Md5])
+/// .users([DigestUser::new("device", "password")])
+/// .build();
+/// ```
+#[derive(Debug)]
+pub struct DefaultDigestAuthorizerBuilder<D, N> {
+    digest_extractor: D,
+    nonce_store: N,
+    realm: Cow<'static, str>,
+    opaque: Option<String>,
+    algorithms: Vec<DigestAlgorithm>,
+    users: HashMap<Cow<'static, str>, DigestUser>,
+}
+
+impl<D, N> DefaultDigestAuthorizerBuilder<D, N> {
+    /// Defaults to `SHA-256` only.
+    pub fn new(digest_extractor: D, nonce_store: N, realm: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            digest_extractor,
+            nonce_store,
+            realm: realm.into(),
+            opaque: None,
+            algorithms: vec![DigestAlgorithm::Sha256],
+            users: HashMap::new(),
+        }
+    }
+
+    /// The accepted algorithms, offered to clients in this order.
+    pub fn algorithms(mut self, algorithms: &[DigestAlgorithm]) -> Self {
+        self.algorithms = algorithms.to_vec();
+        self
+    }
+
+    /// The `opaque` value sent with every challenge and expected back from clients.
+    ///
+    /// Defaults to a random value generated by [`build`](Self::build). Set the same value on every
+    /// instance behind a load balancer, together with a shared nonce store secret.
+    pub fn opaque(mut self, opaque: impl Into<String>) -> Self {
+        self.opaque = Some(opaque.into());
+        self
+    }
+
+    pub fn users(mut self, users: impl IntoIterator<Item = DigestUser>) -> Self {
+        self.users
+            .extend(users.into_iter().map(|user| (user.username.clone(), user)));
+        self
+    }
+
+    pub fn build(self) -> DefaultDigestAuthorizer<D, N> {
+        let opaque = self.opaque.unwrap_or_else(|| {
+            let mut opaque = [0u8; 16];
+            OsRng.fill_bytes(&mut opaque);
+
+            hex::encode(opaque)
+        });
+
+        DefaultDigestAuthorizer {
+            inner: Arc::new(DefaultDigestAuthorizerInner {
+                digest_extractor: self.digest_extractor,
+                nonce_store: self.nonce_store,
+                realm: self.realm,
+                opaque,
+                algorithms: self.algorithms,
+                users: self.users,
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use axum::response::{IntoResponse, Response};
+    use http::{header, StatusCode};
+
+    use super::DefaultDigestAuthorizeError;
+
+    impl<D, N> IntoResponse for DefaultDigestAuthorizeError<D, N>
+    where
+        D: std::error::Error,
+        N: std::error::Error,
+    {
+        fn into_response(self) -> Response {
+            tracing::warn!(err = %self, "Unauthorized");
+
+            let Some(challenge) = self.challenge() else {
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            };
+
+            let mut response = StatusCode::UNAUTHORIZED.into_response();
+
+            for value in challenge.header_values() {
+                response
+                    .headers_mut()
+                    .append(header::WWW_AUTHENTICATE, value);
+            }
+
+            response
+        }
+    }
+
+    impl<D, N> From<DefaultDigestAuthorizeError<D, N>> for Response
+    where
+        D: std::error::Error,
+        N: std::error::Error,
+    {
+        fn from(value: DefaultDigestAuthorizeError<D, N>) -> Self {
+            value.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use http::{Method, Request};
+
+    use crate::authorize::{
+        digest::nonce::InMemoryNonceStore, header::digest::DefaultDigestExtractor,
+    };
+
+    use super::*;
+
+    fn parts(authorization: Option<String>) -> Parts {
+        let mut request = Request::builder()
+            .method(Method::GET)
+            .uri("/dir/index.html");
+
+        if let Some(authorization) = authorization {
+            request = request.header("authorization", authorization);
+        }
+
+        request.body(()).expect("Valid request").into_parts().0
+    }
+
+    fn authorization(challenge: &DigestChallenge, password: &str, nc: &str) -> String {
+        let algorithm = DigestAlgorithm::Sha256;
+        let cnonce = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+
+        let ha1 = algorithm.hash(&["Mufasa", &challenge.realm, password]);
+        let ha2 = algorithm.hash(&["GET", "/dir/index.html"]);
+        let response = algorithm.hash(&[&ha1, &challenge.nonce, nc, cnonce, "auth", &ha2]);
+
+        format!(
+            r#"Digest username="Mufasa", realm="{}", uri="/dir/index.html", algorithm=SHA-256, nonce="{}", nc={nc}, cnonce="{cnonce}", qop=auth, response="{response}", opaque="{}""#,
+            challenge.realm, challenge.nonce, challenge.opaque
+        )
+    }
+
+    #[tokio::test]
+    async fn digest_responses_are_verified_once() {
+        let authorizer = DefaultDigestAuthorizerBuilder::new(
+            DefaultDigestExtractor::new(),
+            InMemoryNonceStore::new(60, NonZeroUsize::new(16).expect("Non zero")),
+            "http-auth@example.org",
+        )
+        .users([DigestUser::new("Mufasa", "Circle of Life")])
+        .build();
+
+        let err = authorizer
+            .extract(&parts(None))
+            .await
+            .expect_err("Missing authorization should be challenged");
+
+        let challenge = err.challenge().expect("Challenge should be issued").clone();
+
+        let user = authorizer
+            .extract(&parts(Some(authorization(
+                &challenge,
+                "Circle of Life",
+                "00000001",
+            ))))
+            .await
+            .expect("Digest should be valid");
+
+        assert_eq!(user.username, "Mufasa");
+
+        let result = authorizer
+            .extract(&parts(Some(authorization(
+                &challenge,
+                "Circle of Life",
+                "00000001",
+            ))))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(DefaultDigestAuthorizeError::Invalid {
+                reason: DigestFailure::Replayed,
+                ..
+            })
+        ));
+
+        let result = authorizer
+            .extract(&parts(Some(authorization(&challenge, "wrong", "00000002"))))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(DefaultDigestAuthorizeError::Invalid {
+                reason: DigestFailure::Response,
+                ..
+            })
+        ));
+
+        authorizer
+            .extract(&parts(Some(authorization(
+                &challenge,
+                "Circle of Life",
+                "00000002",
+            ))))
+            .await
+            .expect("Next nonce count should be valid");
+    }
+
+    #[tokio::test]
+    async fn challenges_are_accepted_by_instances_sharing_opaque_and_secret() {
+        let authorizer = || {
+            DefaultDigestAuthorizerBuilder::new(
+                DefaultDigestExtractor::new(),
+                InMemoryNonceStore::new(60, NonZeroUsize::new(16).expect("Non zero"))
+                    .secret(b"shared-secret".as_slice()),
+                "http-auth@example.org",
+            )
+            .opaque("shared-opaque")
+            .users([DigestUser::new("Mufasa", "Circle of Life")])
+            .build()
+        };
+
+        let challenge = authorizer()
+            .extract(&parts(None))
+            .await
+            .expect_err("Missing authorization should be challenged")
+            .challenge()
+            .expect("Challenge should be issued")
+            .clone();
+
+        assert_eq!(challenge.opaque, "shared-opaque");
+
+        authorizer()
+            .extract(&parts(Some(authorization(
+                &challenge,
+                "Circle of Life",
+                "00000001",
+            ))))
+            .await
+            .expect("Digest should be valid on another instance");
+    }
+}
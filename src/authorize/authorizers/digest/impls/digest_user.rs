@@ -0,0 +1,28 @@
+use std::borrow::Cow;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct DigestUser {
+    pub username: Cow<'static, str>,
+    pub password: Cow<'static, str>,
+}
+
+impl core::fmt::Debug for DigestUser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DigestUser")
+            .field("username", &self.username)
+            .field("password", &"...")
+            .finish()
+    }
+}
+
+impl DigestUser {
+    pub fn new(
+        username: impl Into<Cow<'static, str>>,
+        password: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+}
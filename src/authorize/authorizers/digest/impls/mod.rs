@@ -0,0 +1,2 @@
+pub mod default_digest_authorizer;
+pub mod digest_user;
@@ -0,0 +1,10 @@
+mod impls;
+pub mod nonce;
+
+pub use impls::{
+    default_digest_authorizer::{
+        DefaultDigestAuthorizeError, DefaultDigestAuthorizer, DefaultDigestAuthorizerBuilder,
+        DigestChallenge, DigestFailure,
+    },
+    digest_user::DigestUser,
+};
@@ -0,0 +1,201 @@
+use std::{
+    convert::Infallible,
+    num::NonZeroUsize,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use hmac::{Hmac, Mac};
+use lru::LruCache;
+use rand::{rngs::OsRng, RngCore};
+use sha2::Sha256;
+
+use crate::authorize::digest::nonce::{NonceStatus, NonceStore};
+
+const PAYLOAD_LEN: usize = 16;
+const TAG_LEN: usize = 16;
+
+/// Issues stateless nonces and keeps the nonce counts of used nonces in memory.
+///
+/// A nonce is a timestamp and random bytes, signed with a secret.
+/// Issuing a nonce stores nothing, so unauthenticated clients can not evict the nonces of other clients.
+///
+/// Nonce counts are stored when a nonce is first used by an authenticated request.
+/// The least recently used counts are evicted once `capacity` is reached,
+/// after which the evicted nonce could be replayed until it is `max_age_in_seconds` old.
+pub struct InMemoryNonceStore {
+    secret: Vec<u8>,
+    nonce_counts: Mutex<LruCache<String, u64>>,
+    max_age: Duration,
+}
+
+impl core::fmt::Debug for InMemoryNonceStore {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InMemoryNonceStore")
+            .field("secret", &"...")
+            .field("nonce_counts", &self.nonce_counts)
+            .field("max_age", &self.max_age)
+            .finish()
+    }
+}
+
+impl InMemoryNonceStore {
+    /// Signs nonces with a random secret, use [`secret`](Self::secret) to set a fixed one.
+    pub fn new(max_age_in_seconds: u64, capacity: NonZeroUsize) -> Self {
+        let mut secret = vec![0u8; 32];
+        OsRng.fill_bytes(&mut secret);
+
+        Self {
+            secret,
+            nonce_counts: Mutex::new(LruCache::new(capacity)),
+            max_age: Duration::from_secs(max_age_in_seconds),
+        }
+    }
+
+    /// The secret nonces are signed with.
+    ///
+    /// Nonces issued with a fixed secret stay valid across restarts and on every instance sharing the secret.
+    /// Nonce counts are still kept per instance.
+    pub fn secret(mut self, secret: impl Into<Vec<u8>>) -> Self {
+        self.secret = secret.into();
+        self
+    }
+
+    fn mac(&self, payload: &[u8]) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC can take key of any size");
+
+        mac.update(payload);
+        mac
+    }
+
+    /// The time the nonce was issued at, if it was issued by this store.
+    fn issued_at(&self, nonce: &str) -> Option<SystemTime> {
+        let bytes = hex::decode(nonce).ok()?;
+
+        if bytes.len() != PAYLOAD_LEN + TAG_LEN {
+            return None;
+        }
+
+        let (payload, tag) = bytes.split_at(PAYLOAD_LEN);
+
+        self.mac(payload).verify_truncated_left(tag).ok()?;
+
+        let seconds = u64::from_be_bytes(payload[..8].try_into().ok()?);
+
+        UNIX_EPOCH.checked_add(Duration::from_secs(seconds))
+    }
+}
+
+impl NonceStore for InMemoryNonceStore {
+    type Error = Infallible;
+
+    async fn issue(&self) -> Result<String, Self::Error> {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        let mut payload = [0u8; PAYLOAD_LEN];
+        payload[..8].copy_from_slice(&seconds.to_be_bytes());
+        OsRng.fill_bytes(&mut payload[8..]);
+
+        let tag = self.mac(&payload).finalize().into_bytes();
+
+        Ok(format!(
+            "{}{}",
+            hex::encode(payload),
+            hex::encode(&tag[..TAG_LEN])
+        ))
+    }
+
+    async fn consume(&self, nonce: &str, nc: u64) -> Result<NonceStatus, Self::Error> {
+        let Some(issued_at) = self.issued_at(nonce) else {
+            return Ok(NonceStatus::Unknown);
+        };
+
+        let mut nonce_counts = self
+            .nonce_counts
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+
+        let age = SystemTime::now()
+            .duration_since(issued_at)
+            .unwrap_or_default();
+
+        if age > self.max_age {
+            nonce_counts.pop(nonce);
+
+            return Ok(NonceStatus::Stale);
+        }
+
+        if let Some(last_nc) = nonce_counts.get_mut(nonce) {
+            if nc <= *last_nc {
+                return Ok(NonceStatus::Replayed);
+            }
+
+            *last_nc = nc;
+
+            return Ok(NonceStatus::Valid);
+        }
+
+        nonce_counts.put(nonce.to_string(), nc);
+
+        Ok(NonceStatus::Valid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn issuing_nonces_does_not_evict_used_ones() {
+        let store = InMemoryNonceStore::new(60, NonZeroUsize::new(1).expect("Non zero"));
+
+        let nonce = store.issue().await.unwrap();
+
+        assert_eq!(store.consume(&nonce, 1).await.unwrap(), NonceStatus::Valid);
+
+        for _ in 0..16 {
+            store.issue().await.unwrap();
+        }
+
+        assert_eq!(
+            store.consume(&nonce, 1).await.unwrap(),
+            NonceStatus::Replayed
+        );
+
+        let mut forged = nonce.into_bytes();
+        forged[0] = if forged[0] == b'0' { b'1' } else { b'0' };
+        let forged = String::from_utf8(forged).expect("Valid UTF-8");
+
+        assert_eq!(
+            store.consume(&forged, 2).await.unwrap(),
+            NonceStatus::Unknown
+        );
+
+        let other = InMemoryNonceStore::new(60, NonZeroUsize::new(1).expect("Non zero"));
+
+        assert_eq!(
+            other
+                .consume(&store.issue().await.unwrap(), 1)
+                .await
+                .unwrap(),
+            NonceStatus::Unknown
+        );
+    }
+
+    #[tokio::test]
+    async fn nonces_are_valid_for_stores_sharing_the_secret() {
+        let store = InMemoryNonceStore::new(60, NonZeroUsize::new(1).expect("Non zero"))
+            .secret(b"shared-secret".as_slice());
+
+        let other = InMemoryNonceStore::new(60, NonZeroUsize::new(1).expect("Non zero"))
+            .secret(b"shared-secret".as_slice());
+
+        let nonce = store.issue().await.unwrap();
+
+        assert_eq!(other.consume(&nonce, 1).await.unwrap(), NonceStatus::Valid);
+    }
+}
@@ -0,0 +1,5 @@
+mod impls;
+mod nonce_store;
+
+pub use impls::in_memory_nonce_store::InMemoryNonceStore;
+pub use nonce_store::{NonceStatus, NonceStore};
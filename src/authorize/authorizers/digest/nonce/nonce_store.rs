@@ -0,0 +1,29 @@
+use std::future::Future;
+
+/// The result of using a nonce with a nonce count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonceStatus {
+    Valid,
+    /// The nonce was never issued or was already evicted.
+    Unknown,
+    /// The nonce was issued, but is too old. The client may retry with a new nonce without asking the user.
+    Stale,
+    /// The nonce count was not greater than the last one used with this nonce.
+    Replayed,
+}
+
+/// Issues Digest nonces and tracks their nonce counts to block replays.
+pub trait NonceStore {
+    type Error;
+
+    fn issue(&self) -> impl Future<Output = Result<String, Self::Error>> + Send;
+
+    /// Records that `nonce` was used with the nonce count `nc`.
+    ///
+    /// Must only return [`NonceStatus::Valid`] if `nc` is greater than every count used before with this nonce.
+    fn consume(
+        &self,
+        nonce: &str,
+        nc: u64,
+    ) -> impl Future<Output = Result<NonceStatus, Self::Error>> + Send;
+}
@@ -0,0 +1,451 @@
+use std::{
+    num::ParseIntError,
+    ops::Deref,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use http::request::Parts;
+use sha1::Sha1;
+use sha2::Sha256;
+
+use crate::{
+    authorize::header::{DefaultHeaderError, DefaultHeaderExtractor, HeaderExtractor},
+    extract::{BufferedBody, Extractor},
+};
+
+use super::{
+    hmac_secret::HmacSecret,
+    validation::{HmacAlgorithm, HmacSignatureValidation, SignatureEncoding},
+};
+
+#[derive(Debug)]
+pub struct DefaultHmacSignatureAuthorizerInner<H> {
+    signature_header_extractor: H,
+    timestamp_header_extractor: Option<DefaultHeaderExtractor>,
+    secrets: Vec<HmacSecret>,
+    validation: HmacSignatureValidation,
+}
+
+impl<H> DefaultHmacSignatureAuthorizerInner<H> {
+    pub fn new(
+        signature_header_extractor: H,
+        secrets: Vec<HmacSecret>,
+        validation: HmacSignatureValidation,
+    ) -> Self {
+        Self {
+            signature_header_extractor,
+            timestamp_header_extractor: validation
+                .timestamp_header
+                .clone()
+                .map(DefaultHeaderExtractor::new),
+            secrets,
+            validation,
+        }
+    }
+
+    fn decode_signature(&self, signature: &str) -> Result<Vec<u8>, DefaultHmacSignatureError> {
+        let signature = match &self.validation.signature_prefix {
+            Some(prefix) => signature
+                .strip_prefix(prefix.as_ref())
+                .ok_or(DefaultHmacSignatureError::Prefix)?,
+            None => signature,
+        };
+
+        match self.validation.encoding {
+            SignatureEncoding::Hex => {
+                hex::decode(signature).map_err(DefaultHmacSignatureError::HexDecode)
+            }
+            SignatureEncoding::Base64 => base64::engine::general_purpose::STANDARD
+                .decode(signature)
+                .map_err(DefaultHmacSignatureError::Base64Decode),
+        }
+    }
+
+    fn validate_timestamp(&self, timestamp: &str) -> Result<u64, DefaultHmacSignatureError> {
+        let timestamp = timestamp
+            .parse::<u64>()
+            .map_err(DefaultHmacSignatureError::TimestampParse)?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        if now.abs_diff(timestamp) > self.validation.timestamp_tolerance_in_seconds {
+            return Err(DefaultHmacSignatureError::TimestampTolerance { timestamp });
+        }
+
+        Ok(timestamp)
+    }
+
+    fn signed_payload<'a>(
+        &'a self,
+        parts: &'a Parts,
+        timestamp: Option<&'a str>,
+        body: &'a [u8],
+    ) -> Vec<&'a [u8]> {
+        let validation = &self.validation;
+
+        let components = [
+            validation.payload_prefix.as_deref().map(str::as_bytes),
+            validation
+                .include_method
+                .then(|| parts.method.as_str().as_bytes()),
+            validation.include_path.then(|| parts.uri.path().as_bytes()),
+            timestamp.map(str::as_bytes),
+            Some(body),
+        ];
+
+        let mut payload = Vec::with_capacity(components.len() * 2);
+
+        for component in components.into_iter().flatten() {
+            if !payload.is_empty() {
+                payload.push(validation.separator.as_bytes());
+            }
+
+            payload.push(component);
+        }
+
+        payload
+    }
+
+    /// Verifies the signature against every configured secret and returns the matching one.
+    ///
+    /// Each comparison is constant-time.
+    pub fn verify(&self, payload: &[&[u8]], signature: &[u8]) -> Option<&HmacSecret> {
+        self.secrets
+            .iter()
+            .find(|secret| match self.validation.algorithm {
+                HmacAlgorithm::Sha1 => verify_mac::<Hmac<Sha1>>(&secret.value, payload, signature),
+                HmacAlgorithm::Sha256 => {
+                    verify_mac::<Hmac<Sha256>>(&secret.value, payload, signature)
+                }
+            })
+    }
+}
+
+fn verify_mac<M: Mac + hmac::digest::KeyInit>(
+    key: &[u8],
+    payload: &[&[u8]],
+    signature: &[u8],
+) -> bool {
+    let mut mac = <M as Mac>::new_from_slice(key).expect("HMAC can take a key of any size");
+
+    for component in payload {
+        mac.update(component);
+    }
+
+    mac.verify_slice(signature).is_ok()
+}
+
+#[derive(Debug)]
+pub struct DefaultHmacSignatureAuthorizer<H> {
+    inner: Arc<DefaultHmacSignatureAuthorizerInner<H>>,
+}
+
+impl<H> DefaultHmacSignatureAuthorizer<H> {
+    pub fn new(
+        signature_header_extractor: H,
+        secrets: Vec<HmacSecret>,
+        validation: HmacSignatureValidation,
+    ) -> Self {
+        Self {
+            inner: Arc::new(DefaultHmacSignatureAuthorizerInner::new(
+                signature_header_extractor,
+                secrets,
+                validation,
+            )),
+        }
+    }
+}
+
+impl<H> Clone for DefaultHmacSignatureAuthorizer<H> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<H> Deref for DefaultHmacSignatureAuthorizer<H> {
+    type Target = DefaultHmacSignatureAuthorizerInner<H>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifiedHmacSignature {
+    /// The id of the secret that produced the signature.
+    pub secret_id: String,
+    pub timestamp: Option<u64>,
+}
+
+impl<H> Extractor for DefaultHmacSignatureAuthorizer<H>
+where
+    H: HeaderExtractor + Send + Sync,
+{
+    type Extracted = VerifiedHmacSignature;
+
+    type Error = DefaultHmacSignatureAuthorizeError<H::Error>;
+
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+        let signature = self
+            .signature_header_extractor
+            .extract_header(&parts.headers)
+            .map_err(DefaultHmacSignatureAuthorizeError::Header)?;
+
+        let signature = self.decode_signature(signature)?;
+
+        let timestamp = match &self.timestamp_header_extractor {
+            Some(extractor) => Some(
+                extractor
+                    .extract_header(&parts.headers)
+                    .map_err(DefaultHmacSignatureError::TimestampHeader)?,
+            ),
+            None => None,
+        };
+
+        let validated_timestamp = timestamp
+            .map(|timestamp| self.validate_timestamp(timestamp))
+            .transpose()?;
+
+        let body =
+            BufferedBody::from_parts(parts).ok_or(DefaultHmacSignatureAuthorizeError::Body)?;
+
+        let payload = self.signed_payload(parts, timestamp, body);
+
+        let secret = self
+            .verify(&payload, &signature)
+            .ok_or(DefaultHmacSignatureError::Invalid)?;
+
+        Ok(VerifiedHmacSignature {
+            secret_id: secret.id.to_string(),
+            timestamp: validated_timestamp,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DefaultHmacSignatureAuthorizeError<H> {
+    #[error("Signature header extraction error: {0}")]
+    Header(#[source] H),
+    #[error("Body not found. Did you use `BodyExtensionLayer`?")]
+    Body,
+    #[error("Signature validation error: {0}")]
+    Signature(
+        #[source]
+        #[from]
+        DefaultHmacSignatureError,
+    ),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DefaultHmacSignatureError {
+    #[error("Signature does not start with the expected prefix")]
+    Prefix,
+    #[error("Signature hex decode error: {0}")]
+    HexDecode(#[source] hex::FromHexError),
+    #[error("Signature base64 decode error: {0}")]
+    Base64Decode(#[source] base64::DecodeError),
+    #[error("Timestamp header extraction error: {0}")]
+    TimestampHeader(#[source] DefaultHeaderError),
+    #[error("Timestamp parse error: {0}")]
+    TimestampParse(#[source] ParseIntError),
+    #[error("Timestamp is outside of the tolerance window: {timestamp}")]
+    TimestampTolerance { timestamp: u64 },
+    #[error("Invalid signature")]
+    Invalid,
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use axum::response::{IntoResponse, Response};
+    use http::StatusCode;
+
+    use super::DefaultHmacSignatureAuthorizeError;
+
+    impl<H> IntoResponse for DefaultHmacSignatureAuthorizeError<H>
+    where
+        H: std::error::Error,
+    {
+        fn into_response(self) -> Response {
+            tracing::warn!(err = %self, "Unauthorized");
+
+            match self {
+                DefaultHmacSignatureAuthorizeError::Body => {
+                    StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                _ => StatusCode::UNAUTHORIZED.into_response(),
+            }
+        }
+    }
+
+    impl<H> From<DefaultHmacSignatureAuthorizeError<H>> for Response
+    where
+        H: std::error::Error,
+    {
+        fn from(value: DefaultHmacSignatureAuthorizeError<H>) -> Self {
+            value.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use http::Request;
+
+    use super::*;
+
+    const GITHUB_SECRET: &[u8] = b"It's a Secret to Everybody";
+    const GITHUB_PAYLOAD: &[u8] = b"Hello, World!";
+    const GITHUB_SIGNATURE: &str =
+        "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+    fn parts(headers: &[(&str, &str)], body: &'static [u8]) -> Parts {
+        let mut builder = Request::builder().method("POST").uri("/webhook");
+
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+
+        let (mut parts, _) = builder.body(()).expect("Valid request").into_parts();
+
+        parts
+            .extensions
+            .insert(BufferedBody(Bytes::from_static(body)));
+
+        parts
+    }
+
+    fn github_authorizer(
+        secrets: Vec<HmacSecret>,
+    ) -> DefaultHmacSignatureAuthorizer<DefaultHeaderExtractor> {
+        DefaultHmacSignatureAuthorizer::new(
+            DefaultHeaderExtractor::new("x-hub-signature-256"),
+            secrets,
+            HmacSignatureValidation::new().signature_prefix("sha256="),
+        )
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs()
+    }
+
+    fn sign(secret: &[u8], payload: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("Valid key");
+        mac.update(payload);
+
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[tokio::test]
+    async fn github_signature_is_valid() {
+        let authorizer = github_authorizer(vec![HmacSecret::new("github", GITHUB_SECRET)]);
+
+        let parts = parts(&[("x-hub-signature-256", GITHUB_SIGNATURE)], GITHUB_PAYLOAD);
+
+        let verified = authorizer.extract(&parts).await.expect("Valid signature");
+
+        assert_eq!(verified.secret_id, "github");
+    }
+
+    #[tokio::test]
+    async fn rotated_secret_is_accepted() {
+        let authorizer = github_authorizer(vec![
+            HmacSecret::new("new", b"new-secret".as_slice()),
+            HmacSecret::new("old", GITHUB_SECRET),
+        ]);
+
+        let parts = parts(&[("x-hub-signature-256", GITHUB_SIGNATURE)], GITHUB_PAYLOAD);
+
+        let verified = authorizer.extract(&parts).await.expect("Valid signature");
+
+        assert_eq!(verified.secret_id, "old");
+    }
+
+    #[tokio::test]
+    async fn tampered_body_is_rejected() {
+        let authorizer = github_authorizer(vec![HmacSecret::new("github", GITHUB_SECRET)]);
+
+        let parts = parts(
+            &[("x-hub-signature-256", GITHUB_SIGNATURE)],
+            b"Hello, World?",
+        );
+
+        let result = authorizer.extract(&parts).await;
+
+        assert!(matches!(
+            result,
+            Err(DefaultHmacSignatureAuthorizeError::Signature(
+                DefaultHmacSignatureError::Invalid
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn timestamp_outside_of_tolerance_is_rejected() {
+        let authorizer = DefaultHmacSignatureAuthorizer::new(
+            DefaultHeaderExtractor::new("x-slack-signature"),
+            vec![HmacSecret::new("slack", b"slack-secret".as_slice())],
+            HmacSignatureValidation::new()
+                .signature_prefix("v0=")
+                .payload_prefix("v0")
+                .separator(":")
+                .timestamp_header("x-slack-request-timestamp")
+                .timestamp_tolerance_in_seconds(60),
+        );
+
+        let fresh = now().to_string();
+        let signature = format!(
+            "v0={}",
+            sign(b"slack-secret", format!("v0:{fresh}:body").as_bytes())
+        );
+
+        let parts_fresh = parts(
+            &[
+                ("x-slack-signature", &signature),
+                ("x-slack-request-timestamp", &fresh),
+            ],
+            b"body",
+        );
+
+        let verified = authorizer
+            .extract(&parts_fresh)
+            .await
+            .expect("Valid signature");
+
+        assert_eq!(verified.timestamp, Some(fresh.parse().unwrap()));
+
+        let stale = (now() - 120).to_string();
+        let signature = format!(
+            "v0={}",
+            sign(b"slack-secret", format!("v0:{stale}:body").as_bytes())
+        );
+
+        let parts_stale = parts(
+            &[
+                ("x-slack-signature", &signature),
+                ("x-slack-request-timestamp", &stale),
+            ],
+            b"body",
+        );
+
+        let result = authorizer.extract(&parts_stale).await;
+
+        assert!(matches!(
+            result,
+            Err(DefaultHmacSignatureAuthorizeError::Signature(
+                DefaultHmacSignatureError::TimestampTolerance { .. }
+            ))
+        ));
+    }
+}
@@ -0,0 +1,25 @@
+use std::borrow::Cow;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct HmacSecret {
+    pub id: Cow<'static, str>,
+    pub value: Cow<'static, [u8]>,
+}
+
+impl core::fmt::Debug for HmacSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HmacSecret")
+            .field("id", &self.id)
+            .field("value", &"...")
+            .finish()
+    }
+}
+
+impl HmacSecret {
+    pub fn new(id: impl Into<Cow<'static, str>>, value: impl Into<Cow<'static, [u8]>>) -> Self {
+        Self {
+            id: id.into(),
+            value: value.into(),
+        }
+    }
+}
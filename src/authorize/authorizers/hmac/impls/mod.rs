@@ -0,0 +1,6 @@
+pub mod default_hmac_signature_authorizer;
+mod hmac_secret;
+mod validation;
+
+pub use hmac_secret::HmacSecret;
+pub use validation::{HmacAlgorithm, HmacSignatureValidation, SignatureEncoding};
@@ -0,0 +1,116 @@
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HmacAlgorithm {
+    Sha1,
+    Sha256,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureEncoding {
+    Hex,
+    Base64,
+}
+
+/// Describes how the signed payload is built and how the signature header is read.
+///
+/// The signed payload is built by joining the following components with the separator:
+/// the payload prefix, the method, the path, the timestamp and the body.
+/// Components that are not configured are left out.
+///
+/// # Examples
+///
+/// GitHub (`X-Hub-Signature-256: sha256=<hex>` over the body):
+///
+/// ```rust,ignore
+/// HmacSignatureValidation::new().signature_prefix("sha256=")
+/// ```
+///
+/// Slack (`X-Slack-Signature: v0=<hex>` over `v0:<timestamp>:<body>`):
+///
+/// ```rust,ignore
+/// HmacSignatureValidation::new()
+///     .signature_prefix("v0=")
+///     .payload_prefix("v0")
+///     .separator(":")
+///     .timestamp_header("x-slack-request-timestamp")
+/// ```
+#[derive(Debug, Clone)]
+pub struct HmacSignatureValidation {
+    pub(crate) algorithm: HmacAlgorithm,
+    pub(crate) encoding: SignatureEncoding,
+    pub(crate) signature_prefix: Option<Cow<'static, str>>,
+    pub(crate) payload_prefix: Option<Cow<'static, str>>,
+    pub(crate) separator: Cow<'static, str>,
+    pub(crate) include_method: bool,
+    pub(crate) include_path: bool,
+    pub(crate) timestamp_header: Option<Cow<'static, str>>,
+    pub(crate) timestamp_tolerance_in_seconds: u64,
+}
+
+impl Default for HmacSignatureValidation {
+    fn default() -> Self {
+        Self {
+            algorithm: HmacAlgorithm::Sha256,
+            encoding: SignatureEncoding::Hex,
+            signature_prefix: None,
+            payload_prefix: None,
+            separator: Cow::Borrowed("."),
+            include_method: false,
+            include_path: false,
+            timestamp_header: None,
+            timestamp_tolerance_in_seconds: 300,
+        }
+    }
+}
+
+impl HmacSignatureValidation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn algorithm(mut self, algorithm: HmacAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    pub fn encoding(mut self, encoding: SignatureEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn signature_prefix(mut self, signature_prefix: impl Into<Cow<'static, str>>) -> Self {
+        self.signature_prefix = Some(signature_prefix.into());
+        self
+    }
+
+    pub fn payload_prefix(mut self, payload_prefix: impl Into<Cow<'static, str>>) -> Self {
+        self.payload_prefix = Some(payload_prefix.into());
+        self
+    }
+
+    pub fn separator(mut self, separator: impl Into<Cow<'static, str>>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    pub fn include_method(mut self, include_method: bool) -> Self {
+        self.include_method = include_method;
+        self
+    }
+
+    pub fn include_path(mut self, include_path: bool) -> Self {
+        self.include_path = include_path;
+        self
+    }
+
+    pub fn timestamp_header(mut self, timestamp_header: impl Into<Cow<'static, str>>) -> Self {
+        self.timestamp_header = Some(timestamp_header.into());
+        self
+    }
+
+    pub fn timestamp_tolerance_in_seconds(mut self, timestamp_tolerance_in_seconds: u64) -> Self {
+        self.timestamp_tolerance_in_seconds = timestamp_tolerance_in_seconds;
+        self
+    }
+}
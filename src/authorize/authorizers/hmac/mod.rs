@@ -0,0 +1,8 @@
+mod impls;
+
+pub use impls::{
+    default_hmac_signature_authorizer::{
+        DefaultHmacSignatureAuthorizeError, DefaultHmacSignatureAuthorizer, VerifiedHmacSignature,
+    },
+    HmacAlgorithm, HmacSecret, HmacSignatureValidation, SignatureEncoding,
+};
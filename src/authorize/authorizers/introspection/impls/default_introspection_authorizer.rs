@@ -0,0 +1,273 @@
+use std::{marker::PhantomData, ops::Deref, sync::Arc};
+
+use http::request::Parts;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    authorize::{header::bearer::BearerExtractor, introspection::introspect::TokenIntrospector},
+    extract::Extractor,
+};
+
+#[derive(Debug)]
+pub struct DefaultIntrospectionAuthorizerInner<Be, I, C> {
+    bearer_extractor: Be,
+    token_introspector: I,
+    _claims: PhantomData<C>,
+}
+
+impl<Be, I, C> DefaultIntrospectionAuthorizerInner<Be, I, C> {
+    const fn new(bearer_extractor: Be, token_introspector: I) -> Self {
+        Self {
+            bearer_extractor,
+            token_introspector,
+            _claims: PhantomData,
+        }
+    }
+
+    pub fn validate(
+        &self,
+        response: serde_json::Value,
+    ) -> Result<C, DefaultIntrospectionValidationError>
+    where
+        C: DeserializeOwned,
+    {
+        let active = response
+            .get("active")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        if !active {
+            return Err(DefaultIntrospectionValidationError::Inactive);
+        }
+
+        serde_json::from_value(response).map_err(DefaultIntrospectionValidationError::Claims)
+    }
+}
+
+#[derive(Debug)]
+pub struct DefaultIntrospectionAuthorizer<Be, I, C> {
+    inner: Arc<DefaultIntrospectionAuthorizerInner<Be, I, C>>,
+}
+
+impl<Be, I, C> Clone for DefaultIntrospectionAuthorizer<Be, I, C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<Be, I, C> Deref for DefaultIntrospectionAuthorizer<Be, I, C> {
+    type Target = DefaultIntrospectionAuthorizerInner<Be, I, C>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<Be, I, C> DefaultIntrospectionAuthorizer<Be, I, C> {
+    pub fn new(bearer_extractor: Be, token_introspector: I) -> Self {
+        Self {
+            inner: Arc::new(DefaultIntrospectionAuthorizerInner::new(
+                bearer_extractor,
+                token_introspector,
+            )),
+        }
+    }
+}
+
+impl<Be, I, C> Extractor for DefaultIntrospectionAuthorizer<Be, I, C>
+where
+    Be: BearerExtractor + Send + Sync,
+    I: TokenIntrospector + Send + Sync,
+    C: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    type Extracted = C;
+
+    type Error = DefaultIntrospectionAuthorizeError<Be::Error, I::Error>;
+
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+        let bearer = self
+            .bearer_extractor
+            .extract_bearer(&parts.headers)
+            .map_err(DefaultIntrospectionAuthorizeError::Bearer)?;
+
+        let response = self
+            .token_introspector
+            .introspect(bearer)
+            .await
+            .map_err(DefaultIntrospectionAuthorizeError::Introspection)?;
+
+        self.validate(response)
+            .map_err(DefaultIntrospectionAuthorizeError::Token)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DefaultIntrospectionAuthorizeError<Be, I> {
+    #[error("Bearer extraction error: {0}")]
+    Bearer(#[source] Be),
+    #[error("Introspection error: {0}")]
+    Introspection(#[source] I),
+    #[error("Token validation error: {0}")]
+    Token(
+        #[source]
+        #[from]
+        DefaultIntrospectionValidationError,
+    ),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DefaultIntrospectionValidationError {
+    #[error("Token is not active")]
+    Inactive,
+    #[error("Claims deserialization error: {0}")]
+    Claims(#[source] serde_json::Error),
+}
+
+/// Like [`DefaultJwtAuthorizerBuilder`](crate::authorize::jwt::DefaultJwtAuthorizerBuilder),
+/// this builder lets you name the claims type at the end.
+///
+/// ```rust,ignore
+/// let introspection_authorizer =
+///     DefaultIntrospectionAuthorizerBuilder::new(bearer_extractor, token_introspector)
+///         .build::<Claims>();
+/// ```
+#[derive(Debug)]
+pub struct DefaultIntrospectionAuthorizerBuilder<Be, I> {
+    bearer_extractor: Be,
+    token_introspector: I,
+}
+
+impl<Be, I> DefaultIntrospectionAuthorizerBuilder<Be, I> {
+    pub fn new(bearer_extractor: Be, token_introspector: I) -> Self {
+        Self {
+            bearer_extractor,
+            token_introspector,
+        }
+    }
+
+    pub fn build<C>(self) -> DefaultIntrospectionAuthorizer<Be, I, C> {
+        DefaultIntrospectionAuthorizer::new(self.bearer_extractor, self.token_introspector)
+    }
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use axum::response::{IntoResponse, Response};
+    use http::{header, StatusCode};
+
+    use super::DefaultIntrospectionAuthorizeError;
+
+    impl<Be, I> IntoResponse for DefaultIntrospectionAuthorizeError<Be, I>
+    where
+        Be: std::error::Error,
+        I: std::error::Error,
+    {
+        fn into_response(self) -> Response {
+            tracing::warn!(err = %self, "Unauthorized");
+
+            let status_code = match self {
+                DefaultIntrospectionAuthorizeError::Bearer(_) => StatusCode::UNAUTHORIZED,
+                DefaultIntrospectionAuthorizeError::Introspection(_) => {
+                    StatusCode::INTERNAL_SERVER_ERROR
+                }
+                DefaultIntrospectionAuthorizeError::Token(_) => StatusCode::UNAUTHORIZED,
+            };
+
+            (status_code, [(header::WWW_AUTHENTICATE, "Bearer")]).into_response()
+        }
+    }
+
+    impl<Be, I> From<DefaultIntrospectionAuthorizeError<Be, I>> for Response
+    where
+        Be: std::error::Error,
+        I: std::error::Error,
+    {
+        fn from(value: DefaultIntrospectionAuthorizeError<Be, I>) -> Self {
+            value.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Request;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use crate::authorize::{
+        header::bearer::DefaultBearerExtractor, introspection::introspect::MockTokenIntrospector,
+    };
+
+    use super::*;
+
+    #[derive(Debug, Clone, Deserialize)]
+    struct Claims {
+        sub: String,
+        scope: String,
+    }
+
+    fn parts(token: &str) -> Parts {
+        Request::builder()
+            .header("authorization", format!("Bearer {token}"))
+            .body(())
+            .expect("Valid request")
+            .into_parts()
+            .0
+    }
+
+    #[tokio::test]
+    async fn active_token_is_accepted() {
+        let mut token_introspector = MockTokenIntrospector::default();
+
+        token_introspector
+            .expect_introspect()
+            .withf(|token| token == "opaque")
+            .times(1)
+            .returning(|_| {
+                Box::pin(async {
+                    Ok(json!({ "active": true, "sub": "user-1", "scope": "read write" }))
+                })
+            });
+
+        let authorizer = DefaultIntrospectionAuthorizerBuilder::new(
+            DefaultBearerExtractor::new(),
+            token_introspector,
+        )
+        .build::<Claims>();
+
+        let claims = authorizer
+            .extract(&parts("opaque"))
+            .await
+            .expect("Token is active");
+
+        assert_eq!(claims.sub, "user-1");
+        assert_eq!(claims.scope, "read write");
+    }
+
+    #[tokio::test]
+    async fn inactive_token_is_rejected() {
+        let mut token_introspector = MockTokenIntrospector::default();
+
+        token_introspector
+            .expect_introspect()
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(json!({ "active": false })) }));
+
+        let authorizer = DefaultIntrospectionAuthorizerBuilder::new(
+            DefaultBearerExtractor::new(),
+            token_introspector,
+        )
+        .build::<Claims>();
+
+        let result = authorizer.extract(&parts("revoked")).await;
+
+        assert!(matches!(
+            result,
+            Err(DefaultIntrospectionAuthorizeError::Token(
+                DefaultIntrospectionValidationError::Inactive
+            ))
+        ));
+    }
+}
@@ -0,0 +1,226 @@
+use crate::authorize::introspection::introspect::TokenIntrospector;
+
+pub struct HttpTokenIntrospector {
+    introspection_uri: String,
+    client_id: String,
+    client_secret: String,
+    http_client: reqwest::Client,
+}
+
+impl core::fmt::Debug for HttpTokenIntrospector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpTokenIntrospector")
+            .field("introspection_uri", &self.introspection_uri)
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"...")
+            .field("http_client", &self.http_client)
+            .finish()
+    }
+}
+
+impl HttpTokenIntrospector {
+    pub const fn new(
+        introspection_uri: String,
+        client_id: String,
+        client_secret: String,
+        http_client: reqwest::Client,
+    ) -> Self {
+        Self {
+            introspection_uri,
+            client_id,
+            client_secret,
+            http_client,
+        }
+    }
+}
+
+impl TokenIntrospector for HttpTokenIntrospector {
+    type Error = HttpTokenIntrospectError;
+
+    async fn introspect(&self, token: &str) -> Result<serde_json::Value, Self::Error> {
+        tracing::debug!("Introspecting token");
+
+        let response = self
+            .http_client
+            .post(&self.introspection_uri)
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .form(&[("token", token), ("token_type_hint", "access_token")])
+            .send()
+            .await
+            .map_err(HttpTokenIntrospectError::Fetch)?
+            .error_for_status()
+            .map_err(HttpTokenIntrospectError::Status)?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(HttpTokenIntrospectError::Parse)?;
+
+        Ok(response)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum HttpTokenIntrospectError {
+    #[error("Failed to send introspection request: {0}")]
+    Fetch(#[source] reqwest::Error),
+    #[error("Introspection endpoint returned an error status: {0}")]
+    Status(#[source] reqwest::Error),
+    #[error("Failed to parse introspection response: {0}")]
+    Parse(#[source] reqwest::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use http::Request;
+    use serde::Deserialize;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use crate::{
+        authorize::{
+            header::bearer::DefaultBearerExtractor,
+            introspection::{
+                DefaultIntrospectionAuthorizeError, DefaultIntrospectionAuthorizerBuilder,
+            },
+        },
+        extract::Extractor,
+    };
+
+    use super::*;
+
+    #[derive(Debug, Clone, Deserialize)]
+    struct Claims {
+        sub: String,
+    }
+
+    /// Answers introspection requests of `client-1`, for which only `active-token` is active.
+    async fn start_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("Bind");
+        let uri = format!(
+            "http://{}/introspect",
+            listener.local_addr().expect("Address")
+        );
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buf = [0; 1024];
+
+                    let header_end = loop {
+                        if let Some(index) =
+                            request.windows(4).position(|window| window == b"\r\n\r\n")
+                        {
+                            break index + 4;
+                        }
+
+                        match stream.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => request.extend_from_slice(&buf[..n]),
+                        }
+                    };
+
+                    let head = String::from_utf8_lossy(&request[..header_end]).to_string();
+
+                    let header = |name: &str| {
+                        head.lines().find_map(|line| {
+                            let (key, value) = line.split_once(':')?;
+
+                            key.eq_ignore_ascii_case(name)
+                                .then(|| value.trim().to_string())
+                        })
+                    };
+
+                    let content_length = header("content-length")
+                        .and_then(|value| value.parse::<usize>().ok())
+                        .unwrap_or(0);
+
+                    while request.len() < header_end + content_length {
+                        match stream.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => request.extend_from_slice(&buf[..n]),
+                        }
+                    }
+
+                    let body = String::from_utf8_lossy(&request[header_end..]).to_string();
+
+                    let authorized = header("authorization")
+                        == Some(format!("Basic {}", STANDARD.encode("client-1:secret-1")));
+
+                    let form_encoded = header("content-type").as_deref()
+                        == Some("application/x-www-form-urlencoded");
+
+                    let (status, response) = if !authorized || !form_encoded {
+                        (401, String::from(r#"{"error":"invalid_client"}"#))
+                    } else if body.split('&').any(|pair| pair == "token=active-token") {
+                        (200, String::from(r#"{"active":true,"sub":"user-1"}"#))
+                    } else {
+                        (200, String::from(r#"{"active":false}"#))
+                    };
+
+                    let response = format!(
+                        "HTTP/1.1 {status} Status\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{response}",
+                        response.len()
+                    );
+
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+
+        uri
+    }
+
+    fn parts(token: &str) -> http::request::Parts {
+        Request::builder()
+            .header("authorization", format!("Bearer {token}"))
+            .body(())
+            .expect("Valid request")
+            .into_parts()
+            .0
+    }
+
+    #[tokio::test]
+    async fn tokens_are_introspected_by_the_server() {
+        let uri = start_server().await;
+
+        let authorizer = DefaultIntrospectionAuthorizerBuilder::new(
+            DefaultBearerExtractor::new(),
+            HttpTokenIntrospector::new(
+                uri.clone(),
+                String::from("client-1"),
+                String::from("secret-1"),
+                reqwest::Client::new(),
+            ),
+        )
+        .build::<Claims>();
+
+        let claims = authorizer
+            .extract(&parts("active-token"))
+            .await
+            .expect("Token should be active");
+
+        assert_eq!(claims.sub, "user-1");
+
+        assert!(matches!(
+            authorizer.extract(&parts("revoked-token")).await,
+            Err(DefaultIntrospectionAuthorizeError::Token(_))
+        ));
+
+        let wrong_secret = HttpTokenIntrospector::new(
+            uri,
+            String::from("client-1"),
+            String::from("wrong"),
+            reqwest::Client::new(),
+        );
+
+        assert!(matches!(
+            wrong_secret.introspect("active-token").await,
+            Err(HttpTokenIntrospectError::Status(_))
+        ));
+
+        assert!(!format!("{wrong_secret:?}").contains("wrong"));
+    }
+}
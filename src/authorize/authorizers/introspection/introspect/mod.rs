@@ -0,0 +1,8 @@
+mod impls;
+mod token_introspector;
+
+pub use impls::http_token_introspector::{HttpTokenIntrospectError, HttpTokenIntrospector};
+pub use token_introspector::{MapError, TokenIntrospector, TokenIntrospectorExt};
+
+#[cfg(test)]
+pub use token_introspector::MockTokenIntrospector;
@@ -0,0 +1,53 @@
+use std::future::Future;
+
+/// Sends a token to an [RFC 7662](https://datatracker.ietf.org/doc/html/rfc7662) introspection endpoint
+/// and returns the raw JSON response.
+#[cfg_attr(test, mockall::automock(type Error=anyhow::Error;))]
+pub trait TokenIntrospector {
+    type Error;
+
+    fn introspect(
+        &self,
+        token: &str,
+    ) -> impl Future<Output = Result<serde_json::Value, Self::Error>> + Send;
+}
+
+pub trait TokenIntrospectorExt: Sized + TokenIntrospector {
+    fn map_err<Fn>(self, map_err: Fn) -> MapError<Self, Fn>;
+}
+
+impl<T> TokenIntrospectorExt for T
+where
+    T: Sized + TokenIntrospector,
+{
+    fn map_err<Fn>(self, map_err: Fn) -> MapError<Self, Fn> {
+        MapError::new(self, map_err)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MapError<T, Fn> {
+    inner: T,
+    map_err: Fn,
+}
+
+impl<T, Fn> MapError<T, Fn> {
+    pub const fn new(inner: T, map_err: Fn) -> Self {
+        Self { inner, map_err }
+    }
+}
+
+impl<I, Fn, E> TokenIntrospector for MapError<I, Fn>
+where
+    I: TokenIntrospector + Sync,
+    Fn: FnOnce(I::Error) -> E + Clone + Sync,
+{
+    type Error = E;
+
+    async fn introspect(&self, token: &str) -> Result<serde_json::Value, Self::Error> {
+        self.inner
+            .introspect(token)
+            .await
+            .map_err(|err| (self.map_err.clone())(err))
+    }
+}
@@ -0,0 +1,7 @@
+mod impls;
+pub mod introspect;
+
+pub use impls::default_introspection_authorizer::{
+    DefaultIntrospectionAuthorizeError, DefaultIntrospectionAuthorizer,
+    DefaultIntrospectionAuthorizerBuilder,
+};
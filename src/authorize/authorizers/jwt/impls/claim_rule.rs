@@ -0,0 +1,200 @@
+use regex::Regex;
+use serde_json::Value;
+
+/// A requirement on a claim of a JWT, checked on the raw claims before they are deserialized.
+///
+/// The claim is addressed by its name (`email_verified`) or, if it starts with `/`,
+/// by a JSON pointer into nested claims (`/realm_access/roles`).
+#[derive(Debug, Clone)]
+pub struct ClaimRule {
+    claim: String,
+    requirement: ClaimRequirement,
+}
+
+#[derive(Debug, Clone)]
+enum ClaimRequirement {
+    Equals(Value),
+    Contains(Value),
+    OneOf(Vec<Value>),
+    Matches(Regex),
+}
+
+impl ClaimRule {
+    fn new(claim: impl Into<String>, requirement: ClaimRequirement) -> Self {
+        Self {
+            claim: claim.into(),
+            requirement,
+        }
+    }
+
+    /// The claim must equal `value`.
+    pub fn equals(claim: impl Into<String>, value: impl Into<Value>) -> Self {
+        Self::new(claim, ClaimRequirement::Equals(value.into()))
+    }
+
+    /// The claim must be an array containing `value`,
+    /// or a space-separated string (like `scope`) containing the string `value`.
+    pub fn contains(claim: impl Into<String>, value: impl Into<Value>) -> Self {
+        Self::new(claim, ClaimRequirement::Contains(value.into()))
+    }
+
+    /// The claim must equal one of `values`.
+    pub fn one_of<V: Into<Value>>(
+        claim: impl Into<String>,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        Self::new(
+            claim,
+            ClaimRequirement::OneOf(values.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// The claim must be a string matching `regex`, or an array containing such a string.
+    pub fn matches(claim: impl Into<String>, regex: &str) -> Result<Self, regex::Error> {
+        Ok(Self::new(
+            claim,
+            ClaimRequirement::Matches(Regex::new(regex)?),
+        ))
+    }
+
+    pub fn claim(&self) -> &str {
+        &self.claim
+    }
+
+    fn lookup<'a>(&self, claims: &'a Value) -> Option<&'a Value> {
+        if self.claim.starts_with('/') {
+            return claims.pointer(&self.claim);
+        }
+
+        claims.get(&self.claim)
+    }
+
+    pub(crate) fn check(&self, claims: &Value) -> Result<(), ClaimRuleError> {
+        let claim = || self.claim.clone();
+
+        let value = self
+            .lookup(claims)
+            .ok_or_else(|| ClaimRuleError::Missing { claim: claim() })?;
+
+        match &self.requirement {
+            ClaimRequirement::Equals(expected) => {
+                if value != expected {
+                    return Err(ClaimRuleError::NotEqual {
+                        claim: claim(),
+                        expected: expected.clone(),
+                    });
+                }
+            }
+            ClaimRequirement::Contains(expected) => {
+                let contains = match (value, expected) {
+                    (Value::Array(values), expected) => values.contains(expected),
+                    (Value::String(values), Value::String(expected)) => {
+                        values.split_whitespace().any(|value| value == expected)
+                    }
+                    _ => false,
+                };
+
+                if !contains {
+                    return Err(ClaimRuleError::NotContained {
+                        claim: claim(),
+                        expected: expected.clone(),
+                    });
+                }
+            }
+            ClaimRequirement::OneOf(allowed) => {
+                if !allowed.contains(value) {
+                    return Err(ClaimRuleError::NotOneOf {
+                        claim: claim(),
+                        allowed: allowed.clone(),
+                    });
+                }
+            }
+            ClaimRequirement::Matches(regex) => {
+                let is_match = |value: &Value| value.as_str().is_some_and(|s| regex.is_match(s));
+
+                let matches = match value {
+                    Value::Array(values) => values.iter().any(is_match),
+                    value => is_match(value),
+                };
+
+                if !matches {
+                    return Err(ClaimRuleError::NoMatch {
+                        claim: claim(),
+                        regex: regex.as_str().to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClaimRuleError {
+    #[error("Claim {claim} is missing")]
+    Missing { claim: String },
+    #[error("Claim {claim} does not equal {expected}")]
+    NotEqual { claim: String, expected: Value },
+    #[error("Claim {claim} does not contain {expected}")]
+    NotContained { claim: String, expected: Value },
+    #[error("Claim {claim} is not one of {allowed:?}")]
+    NotOneOf { claim: String, allowed: Vec<Value> },
+    #[error("Claim {claim} does not match {regex}")]
+    NoMatch { claim: String, regex: String },
+}
+
+impl ClaimRuleError {
+    pub fn claim(&self) -> &str {
+        match self {
+            ClaimRuleError::Missing { claim }
+            | ClaimRuleError::NotEqual { claim, .. }
+            | ClaimRuleError::NotContained { claim, .. }
+            | ClaimRuleError::NotOneOf { claim, .. }
+            | ClaimRuleError::NoMatch { claim, .. } => claim,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn claim_rules_are_checked() {
+        let claims = json!({
+            "email_verified": true,
+            "email": "user@example.com",
+            "scope": "openid read:orders",
+            "groups": ["/users", "/admins"],
+            "realm_access": { "roles": ["offline_access", "admin"] },
+        });
+
+        let satisfied = [
+            ClaimRule::equals("email_verified", true),
+            ClaimRule::contains("groups", "/admins"),
+            ClaimRule::contains("scope", "read:orders"),
+            ClaimRule::contains("/realm_access/roles", "admin"),
+            ClaimRule::one_of("email", ["user@example.com", "admin@example.com"]),
+            ClaimRule::matches("email", r"@example\.com$").expect("Valid regex"),
+        ];
+
+        for rule in satisfied {
+            rule.check(&claims).expect("Rule should be satisfied");
+        }
+
+        let err = ClaimRule::contains("scope", "read")
+            .check(&claims)
+            .expect_err("Partial scopes do not match");
+
+        assert!(matches!(err, ClaimRuleError::NotContained { .. }));
+
+        let err = ClaimRule::equals("/realm_access/client", "account")
+            .check(&claims)
+            .expect_err("Claim is missing");
+
+        assert_eq!(err.claim(), "/realm_access/client");
+    }
+}
@@ -1,15 +1,21 @@
-use std::{marker::PhantomData, ops::Deref, sync::Arc};
-
-use http::HeaderMap;
-use jsonwebtoken::{decode, decode_header, errors::Error as JwtError, jwk::JwkSet, DecodingKey};
-use serde::de::DeserializeOwned;
+use std::{marker::PhantomData, ops::Deref, sync::Arc, time::Instant};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use http::request::Parts;
+use jsonwebtoken::{
+    decode, decode_header,
+    errors::{Error as JwtError, ErrorKind as JwtErrorKind},
+    jwk::{JwkSet, KeyAlgorithm},
+    Algorithm, DecodingKey,
+};
+use serde::{de::DeserializeOwned, Deserialize};
 
 use crate::{
     authorize::{authorizers::jwt::jwk_set::JwkSetProvider, header::bearer::BearerExtractor},
     extract::Extractor,
 };
 
-use super::validation::Validation;
+use super::{claim_rule::ClaimRuleError, key_selection::check_algorithm, validation::Validation};
 
 #[derive(Debug)]
 pub struct DefaultJwtAuthorizerInner<Be, P, C> {
@@ -33,22 +39,138 @@ impl<Be, P, C> DefaultJwtAuthorizerInner<Be, P, C> {
     where
         C: DeserializeOwned,
     {
-        let header = decode_header(jwt).map_err(DefaultJwtValidationError::DecodeHeader)?;
-        let kid = header.kid.ok_or(DefaultJwtValidationError::Kid)?;
+        validate(&self.validation, jwt, jwks)
+    }
+}
+
+pub(crate) fn validate<C>(
+    validation: &Validation,
+    jwt: &str,
+    jwks: &JwkSet,
+) -> Result<C, DefaultJwtValidationError>
+where
+    C: DeserializeOwned,
+{
+    // `none` is not an `Algorithm`, so decoding such a header would fail with a less helpful error.
+    if unverified_algorithm(jwt).is_some_and(|alg| alg.eq_ignore_ascii_case("none")) {
+        return Err(DefaultJwtValidationError::NoneAlgorithm);
+    }
+
+    let header = decode_header(jwt).map_err(DefaultJwtValidationError::DecodeHeader)?;
 
-        let jwk = jwks
-            .find(&kid)
-            .ok_or(DefaultJwtValidationError::MatchingJWK { kid })?;
+    if let Some(algorithms) = &validation.algorithms {
+        if !algorithms.contains(&header.alg) {
+            return Err(DefaultJwtValidationError::DisallowedAlgorithm { alg: header.alg });
+        }
+    }
+
+    let candidates = validation.key_selection.select(&header, jwks)?;
+
+    for jwk in candidates.iter() {
+        check_algorithm(jwk, header.alg)?;
+    }
 
+    let jsonwebtoken_validation = validation.to_jsonwebtoken_validation(header.alg);
+
+    for jwk in candidates.iter() {
         let decoding_key =
             DecodingKey::from_jwk(jwk).map_err(DefaultJwtValidationError::DecodingKey)?;
 
-        let jsonwebtoken_validation = self.validation.to_jsonwebtoken_validation(header.alg);
+        match decode::<serde_json::Value>(jwt, &decoding_key, &jsonwebtoken_validation) {
+            Ok(token_data) => return validate_claims(validation, token_data.claims),
+            // Another candidate may have signed the token.
+            Err(err) if candidates.len() > 1 && *err.kind() == JwtErrorKind::InvalidSignature => {
+                continue
+            }
+            Err(err) => return Err(DefaultJwtValidationError::DecodeData(err)),
+        }
+    }
 
-        let token_data = decode::<C>(jwt, &decoding_key, &jsonwebtoken_validation)
-            .map_err(DefaultJwtValidationError::DecodeData)?;
+    Err(DefaultJwtValidationError::NoCandidateVerified {
+        candidates: candidates.len(),
+    })
+}
 
-        Ok(token_data.claims)
+/// Checks the claim rules on the raw claims before deserializing them.
+fn validate_claims<C>(
+    validation: &Validation,
+    claims: serde_json::Value,
+) -> Result<C, DefaultJwtValidationError>
+where
+    C: DeserializeOwned,
+{
+    for claim_rule in validation.claim_rules.iter() {
+        claim_rule.check(&claims)?;
+    }
+
+    serde_json::from_value(claims).map_err(DefaultJwtValidationError::DeserializeClaims)
+}
+
+/// Reads the `alg` of the JWT header without restricting it to the known algorithms.
+fn unverified_algorithm(jwt: &str) -> Option<String> {
+    #[derive(Deserialize)]
+    struct UnverifiedHeader {
+        alg: String,
+    }
+
+    let header = jwt.split('.').next()?;
+    let header = URL_SAFE_NO_PAD.decode(header).ok()?;
+
+    serde_json::from_slice::<UnverifiedHeader>(&header)
+        .ok()
+        .map(|header| header.alg)
+}
+
+/// Validates the JWT against the JWK set of the given provider.
+///
+/// If the JWT references an unknown `kid`, the provider is asked to refresh its JWK set and the JWT is validated again.
+pub(crate) async fn provide_and_validate<P, C, Be>(
+    jwk_set_provider: &P,
+    validation: &Validation,
+    jwt: &str,
+) -> Result<C, DefaultJwtAuthorizeError<Be, P::Error>>
+where
+    P: JwkSetProvider,
+    C: DeserializeOwned,
+{
+    let read_at = Instant::now();
+
+    let validated = {
+        let jwks = jwk_set_provider
+            .provide_jwk_set()
+            .await
+            .map_err(DefaultJwtAuthorizeError::JwkSet)?;
+
+        validate(validation, jwt, jwks.as_ref())
+    };
+
+    // The JWK set must not be held while refreshing.
+    match validated {
+        Err(err) if err.is_unknown_key() => {
+            tracing::debug!(%err, "Unknown key. Refreshing JWK set");
+
+            match jwk_set_provider
+                .refresh_jwk_set_on_unknown_kid(read_at)
+                .await
+            {
+                Ok(true) => {}
+                Ok(false) => return Err(err.into()),
+                // The token is rejected for its unknown key, not for the failed refresh.
+                Err(_) => {
+                    tracing::warn!(%err, "Failed to refresh JWK set for an unknown key");
+
+                    return Err(err.into());
+                }
+            }
+
+            let jwks = jwk_set_provider
+                .provide_jwk_set()
+                .await
+                .map_err(DefaultJwtAuthorizeError::JwkSet)?;
+
+            validate(validation, jwt, jwks.as_ref()).map_err(DefaultJwtAuthorizeError::Jwt)
+        }
+        validated => validated.map_err(DefaultJwtAuthorizeError::Jwt),
     }
 }
 
@@ -95,20 +217,13 @@ where
 
     type Error = DefaultJwtAuthorizeError<Be::Error, P::Error>;
 
-    async fn extract(&self, headers: &HeaderMap) -> Result<Self::Extracted, Self::Error> {
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
         let bearer = self
             .bearer_extractor
-            .extract_bearer(headers)
+            .extract_bearer(&parts.headers)
             .map_err(DefaultJwtAuthorizeError::Bearer)?;
 
-        let jwks = self
-            .jwk_set_provider
-            .provide_jwk_set()
-            .await
-            .map_err(DefaultJwtAuthorizeError::JwkSet)?;
-
-        self.validate(bearer, jwks.as_ref())
-            .map_err(DefaultJwtAuthorizeError::Jwt)
+        provide_and_validate(&self.jwk_set_provider, &self.validation, bearer).await
     }
 }
 
@@ -130,14 +245,61 @@ pub enum DefaultJwtAuthorizeError<Be, P> {
 pub enum DefaultJwtValidationError {
     #[error("Header decode error: {0}")]
     DecodeHeader(#[source] JwtError),
+    #[error("The none algorithm is not allowed")]
+    NoneAlgorithm,
+    #[error("Algorithm {alg:?} is not allowed")]
+    DisallowedAlgorithm { alg: Algorithm },
+    #[error("Algorithm {alg:?} does not match the JWK algorithm {key_algorithm}")]
+    AlgorithmMismatch {
+        key_algorithm: KeyAlgorithm,
+        alg: Algorithm,
+    },
+    #[error("Algorithm {alg:?} can not be used with a JWK of type {key_type}")]
+    AlgorithmFamilyMismatch {
+        key_type: &'static str,
+        alg: Algorithm,
+    },
     #[error("Kid not found")]
     Kid,
     #[error("No matching JWK found for the given kid: {kid}")]
     MatchingJWK { kid: String },
+    #[error("No JWK fits the algorithm {alg:?} and kid {kid:?}")]
+    NoCandidateJWK { kid: Option<String>, alg: Algorithm },
+    #[error("None of the {candidates} candidate JWKs verified the signature")]
+    NoCandidateVerified { candidates: usize },
+    #[error("Neither x5t#S256 nor x5t found")]
+    Thumbprint,
+    #[error("No matching JWK found for the given thumbprint: {thumbprint}")]
+    MatchingThumbprint { thumbprint: String },
+    #[error("No matching JWK found for the default kid: {kid}")]
+    MatchingDefaultJWK { kid: String },
     #[error("Decoding key creation error : {0}")]
     DecodingKey(#[source] JwtError),
     #[error("Data decode error: {0}")]
     DecodeData(#[source] JwtError),
+    #[error("Claim rule error: {0}")]
+    Claim(
+        #[source]
+        #[from]
+        ClaimRuleError,
+    ),
+    #[error("Claims deserialization error: {0}")]
+    DeserializeClaims(#[source] serde_json::Error),
+}
+
+impl DefaultJwtValidationError {
+    /// Whether the JWK set did not contain the key needed to verify the JWT.
+    ///
+    /// The JWK set might have been rotated since it was last provided.
+    pub fn is_unknown_key(&self) -> bool {
+        matches!(
+            self,
+            DefaultJwtValidationError::MatchingJWK { .. }
+                | DefaultJwtValidationError::NoCandidateJWK { .. }
+                | DefaultJwtValidationError::MatchingThumbprint { .. }
+                | DefaultJwtValidationError::MatchingDefaultJWK { .. }
+        )
+    }
 }
 
 /// A very interesting design choice.
@@ -218,3 +380,346 @@ mod axum {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use base64::Engine;
+    use http::Request;
+    use jsonwebtoken::{
+        encode,
+        jwk::{AlgorithmParameters, CommonParameters, Jwk, OctetKeyParameters, OctetKeyType},
+        EncodingKey, Header,
+    };
+    use serde::{Deserialize, Serialize};
+
+    use crate::authorize::{
+        header::bearer::DefaultBearerExtractor,
+        jwt::{
+            jwk_set::{
+                fetch::MockJwkSetFetcher, rotating::RotatingJwkSetProvider, StaticJwkSetProvider,
+            },
+            KeySelection,
+        },
+    };
+
+    use super::*;
+
+    const SECRET: &[u8] = b"secret";
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct Claims {
+        sub: String,
+        exp: u64,
+    }
+
+    fn jwk_set(kids: &[&str]) -> JwkSet {
+        JwkSet {
+            keys: kids
+                .iter()
+                .map(|kid| Jwk {
+                    common: CommonParameters {
+                        key_id: Some(kid.to_string()),
+                        ..Default::default()
+                    },
+                    algorithm: AlgorithmParameters::OctetKey(OctetKeyParameters {
+                        key_type: OctetKeyType::Octet,
+                        value: base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(SECRET),
+                    }),
+                })
+                .collect(),
+        }
+    }
+
+    fn parts(kid: &str) -> Parts {
+        parts_with_header(Header {
+            kid: Some(kid.to_string()),
+            ..Default::default()
+        })
+    }
+
+    fn parts_with_header(header: Header) -> Parts {
+        let exp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs()
+            + 60;
+
+        let claims = Claims {
+            sub: String::from("user-1"),
+            exp,
+        };
+
+        let jwt = encode(&header, &claims, &EncodingKey::from_secret(SECRET))
+            .expect("Failed to encode jwt");
+
+        Request::builder()
+            .header("authorization", format!("Bearer {jwt}"))
+            .body(())
+            .expect("Valid request")
+            .into_parts()
+            .0
+    }
+
+    #[tokio::test]
+    async fn unknown_kid_triggers_a_refresh() {
+        let mut jwk_set_fetcher = MockJwkSetFetcher::default();
+
+        jwk_set_fetcher
+            .expect_fetch_jwk_set()
+            .times(1)
+            .returning(|| Box::pin(async { Ok(jwk_set(&["old"])) }));
+
+        jwk_set_fetcher
+            .expect_fetch_jwk_set()
+            .times(1)
+            .returning(|| Box::pin(async { Ok(jwk_set(&["old", "new"])) }));
+
+        let jwk_set_provider = RotatingJwkSetProvider::new(60, jwk_set_fetcher)
+            .await
+            .expect("Failed to create rotating jwk set provider");
+
+        let authorizer = DefaultJwtAuthorizerBuilder::new(
+            DefaultBearerExtractor::new(),
+            jwk_set_provider,
+            Validation::new(),
+        )
+        .build::<Claims>();
+
+        let claims = authorizer
+            .extract(&parts("new"))
+            .await
+            .expect("Token should be valid after refresh");
+
+        assert_eq!(claims.sub, "user-1");
+
+        // The fetcher is not called again within the forced refresh interval.
+        let result = authorizer.extract(&parts("unknown")).await;
+
+        assert!(matches!(
+            result,
+            Err(DefaultJwtAuthorizeError::Jwt(
+                DefaultJwtValidationError::MatchingJWK { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_with_a_new_kid_are_accepted() {
+        let mut jwk_set_fetcher = MockJwkSetFetcher::default();
+
+        jwk_set_fetcher
+            .expect_fetch_jwk_set()
+            .times(1)
+            .returning(|| Box::pin(async { Ok(jwk_set(&["old"])) }));
+
+        jwk_set_fetcher
+            .expect_fetch_jwk_set()
+            .times(1)
+            .returning(|| {
+                Box::pin(async {
+                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+                    Ok(jwk_set(&["old", "new"]))
+                })
+            });
+
+        let jwk_set_provider = RotatingJwkSetProvider::new(60, jwk_set_fetcher)
+            .await
+            .expect("Failed to create rotating jwk set provider");
+
+        let authorizer = DefaultJwtAuthorizerBuilder::new(
+            DefaultBearerExtractor::new(),
+            jwk_set_provider,
+            Validation::new(),
+        )
+        .build::<Claims>();
+
+        let parts = parts("new");
+
+        let (first, second, third) = tokio::join!(
+            authorizer.extract(&parts),
+            authorizer.extract(&parts),
+            authorizer.extract(&parts)
+        );
+
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+        assert!(third.is_ok());
+    }
+
+    #[tokio::test]
+    async fn failed_refresh_rejects_the_unknown_key() {
+        let mut jwk_set_fetcher = MockJwkSetFetcher::default();
+
+        jwk_set_fetcher
+            .expect_fetch_jwk_set()
+            .times(1)
+            .returning(|| Box::pin(async { Ok(jwk_set(&["old"])) }));
+
+        jwk_set_fetcher
+            .expect_fetch_jwk_set()
+            .times(1)
+            .returning(|| Box::pin(async { Err(anyhow::anyhow!("Unavailable")) }));
+
+        let jwk_set_provider = RotatingJwkSetProvider::new(60, jwk_set_fetcher)
+            .await
+            .expect("Failed to create rotating jwk set provider");
+
+        let authorizer = DefaultJwtAuthorizerBuilder::new(
+            DefaultBearerExtractor::new(),
+            jwk_set_provider,
+            Validation::new(),
+        )
+        .build::<Claims>();
+
+        let result = authorizer.extract(&parts("new")).await;
+
+        assert!(matches!(
+            result,
+            Err(DefaultJwtAuthorizeError::Jwt(
+                DefaultJwtValidationError::MatchingJWK { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn tokens_without_kid_are_verified_by_key_selection() {
+        let jwk_set_provider = StaticJwkSetProvider::empty()
+            .with_hmac_secret("other", b"other-secret", Algorithm::HS256)
+            .and_then(|provider| provider.with_hmac_secret("key", SECRET, Algorithm::HS256))
+            .expect("Failed to create HMAC keys");
+
+        let authorize = |key_selection| {
+            DefaultJwtAuthorizerBuilder::new(
+                DefaultBearerExtractor::new(),
+                jwk_set_provider.clone(),
+                Validation::new().key_selection(key_selection),
+            )
+            .build::<Claims>()
+        };
+
+        let parts = parts_with_header(Header::default());
+
+        let result = authorize(KeySelection::Kid).extract(&parts).await;
+
+        assert!(matches!(
+            result,
+            Err(DefaultJwtAuthorizeError::Jwt(
+                DefaultJwtValidationError::Kid
+            ))
+        ));
+
+        authorize(KeySelection::Candidates)
+            .extract(&parts)
+            .await
+            .expect("One of the candidates should verify the token");
+
+        authorize(KeySelection::DefaultKey {
+            kid: String::from("key"),
+        })
+        .extract(&parts)
+        .await
+        .expect("The default key should verify the token");
+
+        let result = authorize(KeySelection::DefaultKey {
+            kid: String::from("other"),
+        })
+        .extract(&parts)
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(DefaultJwtAuthorizeError::Jwt(
+                DefaultJwtValidationError::DecodeData(_)
+            ))
+        ));
+
+        let result = authorize(KeySelection::Thumbprint).extract(&parts).await;
+
+        assert!(matches!(
+            result,
+            Err(DefaultJwtAuthorizeError::Jwt(
+                DefaultJwtValidationError::Thumbprint
+            ))
+        ));
+    }
+
+    fn unsigned_parts(header: &str) -> Parts {
+        let header = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(header);
+        let claims = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(r#"{"sub":"user-1"}"#);
+
+        Request::builder()
+            .header(
+                "authorization",
+                format!("Bearer {header}.{claims}.c2lnbmF0dXJl"),
+            )
+            .body(())
+            .expect("Valid request")
+            .into_parts()
+            .0
+    }
+
+    #[tokio::test]
+    async fn algorithm_confusion_is_rejected() {
+        let authorize = |jwk_set_provider, validation| {
+            DefaultJwtAuthorizerBuilder::new(
+                DefaultBearerExtractor::new(),
+                jwk_set_provider,
+                validation,
+            )
+            .build::<Claims>()
+        };
+
+        let any_key = StaticJwkSetProvider::new(jwk_set(&["key"]));
+
+        let result = authorize(any_key.clone(), Validation::new())
+            .extract(&unsigned_parts(r#"{"alg":"none","kid":"key"}"#))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(DefaultJwtAuthorizeError::Jwt(
+                DefaultJwtValidationError::NoneAlgorithm
+            ))
+        ));
+
+        let result = authorize(any_key.clone(), Validation::new())
+            .extract(&unsigned_parts(r#"{"alg":"RS256","kid":"key"}"#))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(DefaultJwtAuthorizeError::Jwt(
+                DefaultJwtValidationError::AlgorithmFamilyMismatch { .. }
+            ))
+        ));
+
+        let result = authorize(any_key, Validation::new().algorithms(&[Algorithm::RS256]))
+            .extract(&parts("key"))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(DefaultJwtAuthorizeError::Jwt(
+                DefaultJwtValidationError::DisallowedAlgorithm { .. }
+            ))
+        ));
+
+        let hs384_key = StaticJwkSetProvider::empty()
+            .with_hmac_secret("key", SECRET, Algorithm::HS384)
+            .expect("Failed to create HMAC key");
+
+        let result = authorize(hs384_key, Validation::new())
+            .extract(&parts("key"))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(DefaultJwtAuthorizeError::Jwt(
+                DefaultJwtValidationError::AlgorithmMismatch { .. }
+            ))
+        ));
+    }
+}
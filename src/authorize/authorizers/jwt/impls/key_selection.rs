@@ -0,0 +1,208 @@
+use jsonwebtoken::{
+    jwk::{AlgorithmParameters, Jwk, JwkSet, KeyAlgorithm, PublicKeyUse},
+    Algorithm, Header,
+};
+
+use super::default_jwt_authorizer::DefaultJwtValidationError;
+
+/// How the keys used to verify a JWT are selected from the JWK set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum KeySelection {
+    /// Use the key with the `kid` from the JWT header. JWTs without a `kid` are rejected.
+    #[default]
+    Kid,
+    /// Try every key whose `alg`, `use` and `kty` fit the algorithm from the JWT header.
+    ///
+    /// If the JWT header has a `kid`, only keys with that `kid` are tried.
+    Candidates,
+    /// Use the key whose `x5t#S256` or `x5t` certificate thumbprint matches the one from the JWT header.
+    Thumbprint,
+    /// Use the key with the `kid` from the JWT header, or the key with the given `kid` if the header has none.
+    DefaultKey { kid: String },
+}
+
+impl KeySelection {
+    /// Returns the keys to try, in order. Never returns an empty list.
+    pub(crate) fn select<'a>(
+        &self,
+        header: &Header,
+        jwks: &'a JwkSet,
+    ) -> Result<Vec<&'a Jwk>, DefaultJwtValidationError> {
+        match self {
+            KeySelection::Kid => {
+                let kid = header.kid.clone().ok_or(DefaultJwtValidationError::Kid)?;
+
+                let jwk = jwks
+                    .find(&kid)
+                    .ok_or(DefaultJwtValidationError::MatchingJWK { kid })?;
+
+                Ok(vec![jwk])
+            }
+            KeySelection::Candidates => {
+                let candidates = jwks
+                    .keys
+                    .iter()
+                    .filter(|jwk| match &header.kid {
+                        Some(kid) => jwk.common.key_id.as_ref() == Some(kid),
+                        None => true,
+                    })
+                    .filter(|jwk| fits(jwk, header.alg))
+                    .collect::<Vec<_>>();
+
+                if candidates.is_empty() {
+                    return Err(DefaultJwtValidationError::NoCandidateJWK {
+                        kid: header.kid.clone(),
+                        alg: header.alg,
+                    });
+                }
+
+                Ok(candidates)
+            }
+            KeySelection::Thumbprint => {
+                let (thumbprint, jwk) = match (&header.x5t_s256, &header.x5t) {
+                    (Some(x5t_s256), _) => (
+                        x5t_s256,
+                        jwks.keys.iter().find(|jwk| {
+                            jwk.common.x509_sha256_fingerprint.as_ref() == Some(x5t_s256)
+                        }),
+                    ),
+                    (None, Some(x5t)) => (
+                        x5t,
+                        jwks.keys
+                            .iter()
+                            .find(|jwk| jwk.common.x509_sha1_fingerprint.as_ref() == Some(x5t)),
+                    ),
+                    (None, None) => return Err(DefaultJwtValidationError::Thumbprint),
+                };
+
+                let jwk = jwk.ok_or_else(|| DefaultJwtValidationError::MatchingThumbprint {
+                    thumbprint: thumbprint.clone(),
+                })?;
+
+                Ok(vec![jwk])
+            }
+            KeySelection::DefaultKey { kid: default_kid } => match &header.kid {
+                Some(_) => KeySelection::Kid.select(header, jwks),
+                None => {
+                    let jwk = jwks.find(default_kid).ok_or_else(|| {
+                        DefaultJwtValidationError::MatchingDefaultJWK {
+                            kid: default_kid.clone(),
+                        }
+                    })?;
+
+                    Ok(vec![jwk])
+                }
+            },
+        }
+    }
+}
+
+/// Checks the declared `alg`, `use` and `kty` of the key against the algorithm of the JWT.
+fn fits(jwk: &Jwk, alg: Algorithm) -> bool {
+    let use_fits = !matches!(
+        jwk.common.public_key_use,
+        Some(PublicKeyUse::Encryption | PublicKeyUse::Other(_))
+    );
+
+    use_fits && check_algorithm(jwk, alg).is_ok()
+}
+
+/// Checks the declared `alg` and the `kty` of the key against the algorithm of the JWT.
+pub(crate) fn check_algorithm(jwk: &Jwk, alg: Algorithm) -> Result<(), DefaultJwtValidationError> {
+    if let Some(key_algorithm) = jwk.common.key_algorithm {
+        if signing_algorithm(key_algorithm) != Some(alg) {
+            return Err(DefaultJwtValidationError::AlgorithmMismatch { key_algorithm, alg });
+        }
+    }
+
+    let kty_fits = match &jwk.algorithm {
+        AlgorithmParameters::OctetKey(_) => {
+            matches!(alg, Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512)
+        }
+        AlgorithmParameters::RSA(_) => matches!(
+            alg,
+            Algorithm::RS256
+                | Algorithm::RS384
+                | Algorithm::RS512
+                | Algorithm::PS256
+                | Algorithm::PS384
+                | Algorithm::PS512
+        ),
+        AlgorithmParameters::EllipticCurve(_) => {
+            matches!(alg, Algorithm::ES256 | Algorithm::ES384)
+        }
+        AlgorithmParameters::OctetKeyPair(_) => matches!(alg, Algorithm::EdDSA),
+    };
+
+    if !kty_fits {
+        return Err(DefaultJwtValidationError::AlgorithmFamilyMismatch {
+            key_type: key_type(jwk),
+            alg,
+        });
+    }
+
+    Ok(())
+}
+
+/// The signing algorithm declared by the `alg` of a key, `None` for encryption algorithms.
+fn signing_algorithm(key_algorithm: KeyAlgorithm) -> Option<Algorithm> {
+    match key_algorithm {
+        KeyAlgorithm::HS256 => Some(Algorithm::HS256),
+        KeyAlgorithm::HS384 => Some(Algorithm::HS384),
+        KeyAlgorithm::HS512 => Some(Algorithm::HS512),
+        KeyAlgorithm::ES256 => Some(Algorithm::ES256),
+        KeyAlgorithm::ES384 => Some(Algorithm::ES384),
+        KeyAlgorithm::RS256 => Some(Algorithm::RS256),
+        KeyAlgorithm::RS384 => Some(Algorithm::RS384),
+        KeyAlgorithm::RS512 => Some(Algorithm::RS512),
+        KeyAlgorithm::PS256 => Some(Algorithm::PS256),
+        KeyAlgorithm::PS384 => Some(Algorithm::PS384),
+        KeyAlgorithm::PS512 => Some(Algorithm::PS512),
+        KeyAlgorithm::EdDSA => Some(Algorithm::EdDSA),
+        KeyAlgorithm::RSA1_5 | KeyAlgorithm::RSA_OAEP | KeyAlgorithm::RSA_OAEP_256 => None,
+    }
+}
+
+fn key_type(jwk: &Jwk) -> &'static str {
+    match &jwk.algorithm {
+        AlgorithmParameters::OctetKey(_) => "oct",
+        AlgorithmParameters::RSA(_) => "RSA",
+        AlgorithmParameters::EllipticCurve(_) => "EC",
+        AlgorithmParameters::OctetKeyPair(_) => "OKP",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonwebtoken::jwk::{CommonParameters, OctetKeyParameters, OctetKeyType};
+
+    use super::*;
+
+    fn oct_jwk(key_algorithm: KeyAlgorithm) -> Jwk {
+        Jwk {
+            common: CommonParameters {
+                key_algorithm: Some(key_algorithm),
+                ..Default::default()
+            },
+            algorithm: AlgorithmParameters::OctetKey(OctetKeyParameters {
+                key_type: OctetKeyType::Octet,
+                value: String::from("c2VjcmV0"),
+            }),
+        }
+    }
+
+    #[test]
+    fn declared_algorithm_must_match_the_jwt_algorithm() {
+        assert!(check_algorithm(&oct_jwk(KeyAlgorithm::HS256), Algorithm::HS256).is_ok());
+
+        assert!(matches!(
+            check_algorithm(&oct_jwk(KeyAlgorithm::HS384), Algorithm::HS256),
+            Err(DefaultJwtValidationError::AlgorithmMismatch { .. })
+        ));
+
+        assert!(matches!(
+            check_algorithm(&oct_jwk(KeyAlgorithm::RSA_OAEP), Algorithm::HS256),
+            Err(DefaultJwtValidationError::AlgorithmMismatch { .. })
+        ));
+    }
+}
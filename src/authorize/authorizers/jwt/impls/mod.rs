@@ -1,4 +1,9 @@
+mod claim_rule;
 pub mod default_jwt_authorizer;
+mod key_selection;
+pub mod multi_issuer_jwt_authorizer;
 mod validation;
 
+pub use claim_rule::{ClaimRule, ClaimRuleError};
+pub use key_selection::KeySelection;
 pub use validation::Validation;
@@ -0,0 +1,344 @@
+use std::{collections::HashMap, marker::PhantomData, ops::Deref, sync::Arc};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use http::request::Parts;
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::{
+    authorize::{authorizers::jwt::jwk_set::JwkSetProvider, header::bearer::BearerExtractor},
+    extract::Extractor,
+};
+
+use super::{
+    default_jwt_authorizer::{
+        provide_and_validate, DefaultJwtAuthorizeError, DefaultJwtValidationError,
+    },
+    validation::Validation,
+};
+
+#[derive(Debug)]
+struct JwtIssuer<P> {
+    jwk_set_provider: P,
+    validation: Validation,
+}
+
+#[derive(Debug)]
+pub struct MultiIssuerJwtAuthorizerInner<Be, P, C> {
+    bearer_extractor: Be,
+    issuers: HashMap<String, JwtIssuer<P>>,
+    _claims: PhantomData<C>,
+}
+
+impl<Be, P, C> MultiIssuerJwtAuthorizerInner<Be, P, C> {
+    pub fn issuers(&self) -> impl Iterator<Item = &str> {
+        self.issuers.keys().map(String::as_str)
+    }
+}
+
+/// Validates JWTs from several issuers, each with its own [`JwkSetProvider`] and [`Validation`].
+///
+/// The issuer is read from the unverified `iss` claim and only used to select the provider and validation.
+/// Tokens from unknown issuers are rejected without touching any provider.
+#[derive(Debug)]
+pub struct MultiIssuerJwtAuthorizer<Be, P, C> {
+    inner: Arc<MultiIssuerJwtAuthorizerInner<Be, P, C>>,
+}
+
+impl<Be, P, C> Clone for MultiIssuerJwtAuthorizer<Be, P, C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<Be, P, C> Deref for MultiIssuerJwtAuthorizer<Be, P, C> {
+    type Target = MultiIssuerJwtAuthorizerInner<Be, P, C>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+/// Reads the `iss` claim of a JWT without validating it.
+fn unverified_issuer(jwt: &str) -> Result<String, MultiIssuerError> {
+    #[derive(Deserialize)]
+    struct UnverifiedClaims {
+        iss: Option<String>,
+    }
+
+    let payload = jwt.split('.').nth(1).ok_or(MultiIssuerError::Malformed)?;
+
+    let payload = URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|_| MultiIssuerError::Malformed)?;
+
+    let claims = serde_json::from_slice::<UnverifiedClaims>(&payload)
+        .map_err(|_| MultiIssuerError::Malformed)?;
+
+    claims.iss.ok_or(MultiIssuerError::MissingIssuer)
+}
+
+impl<Be, P, C> Extractor for MultiIssuerJwtAuthorizer<Be, P, C>
+where
+    Be: BearerExtractor + Send + Sync,
+    P: JwkSetProvider + Send + Sync,
+    C: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    type Extracted = C;
+
+    type Error = MultiIssuerJwtAuthorizeError<Be::Error, P::Error>;
+
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+        let bearer = self
+            .bearer_extractor
+            .extract_bearer(&parts.headers)
+            .map_err(MultiIssuerJwtAuthorizeError::Bearer)?;
+
+        let iss = unverified_issuer(bearer)?;
+
+        let issuer = self
+            .issuers
+            .get(&iss)
+            .ok_or(MultiIssuerError::UnknownIssuer { iss })?;
+
+        provide_and_validate(&issuer.jwk_set_provider, &issuer.validation, bearer)
+            .await
+            .map_err(Into::into)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MultiIssuerJwtAuthorizeError<Be, P> {
+    #[error("Bearer extraction error: {0}")]
+    Bearer(#[source] Be),
+    #[error("Issuer error: {0}")]
+    Issuer(
+        #[source]
+        #[from]
+        MultiIssuerError,
+    ),
+    #[error("JWK set get error: {0}")]
+    JwkSet(#[source] P),
+    #[error("JWT validation error: {0}")]
+    Jwt(#[source] DefaultJwtValidationError),
+}
+
+impl<Be, P> From<DefaultJwtAuthorizeError<Be, P>> for MultiIssuerJwtAuthorizeError<Be, P> {
+    fn from(value: DefaultJwtAuthorizeError<Be, P>) -> Self {
+        match value {
+            DefaultJwtAuthorizeError::Bearer(err) => Self::Bearer(err),
+            DefaultJwtAuthorizeError::JwkSet(err) => Self::JwkSet(err),
+            DefaultJwtAuthorizeError::Jwt(err) => Self::Jwt(err),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MultiIssuerError {
+    #[error("Malformed JWT")]
+    Malformed,
+    #[error("Issuer not found")]
+    MissingIssuer,
+    #[error("Unknown issuer: {iss}")]
+    UnknownIssuer { iss: String },
+}
+
+/// Builds a [`MultiIssuerJwtAuthorizer`].
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let jwt_authorizer = MultiIssuerJwtAuthorizerBuilder::new(bearer_extractor)
+///     .issuer("https://keycloak.com/realms/a", realm_a_jwk_set_provider, Validation::new())
+///     .issuer("https://keycloak.com/realms/b", realm_b_jwk_set_provider, Validation::new())
+///     .build::<Claims>();
+/// ```
+#[derive(Debug)]
+pub struct MultiIssuerJwtAuthorizerBuilder<Be, P> {
+    bearer_extractor: Be,
+    issuers: HashMap<String, JwtIssuer<P>>,
+}
+
+impl<Be, P> MultiIssuerJwtAuthorizerBuilder<Be, P> {
+    pub fn new(bearer_extractor: Be) -> Self {
+        Self {
+            bearer_extractor,
+            issuers: HashMap::new(),
+        }
+    }
+
+    /// Accepts tokens whose `iss` claim is exactly `iss`.
+    ///
+    /// If the `validation` does not restrict the issuer, it is restricted to `iss`.
+    pub fn issuer(
+        mut self,
+        iss: impl Into<String>,
+        jwk_set_provider: P,
+        validation: Validation,
+    ) -> Self {
+        let iss = iss.into();
+
+        let validation = match validation.iss {
+            Some(_) => validation,
+            None => validation.iss(&[&iss]),
+        };
+
+        self.issuers.insert(
+            iss,
+            JwtIssuer {
+                jwk_set_provider,
+                validation,
+            },
+        );
+
+        self
+    }
+
+    pub fn build<C>(self) -> MultiIssuerJwtAuthorizer<Be, P, C> {
+        MultiIssuerJwtAuthorizer {
+            inner: Arc::new(MultiIssuerJwtAuthorizerInner {
+                bearer_extractor: self.bearer_extractor,
+                issuers: self.issuers,
+                _claims: PhantomData,
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use axum::response::{IntoResponse, Response};
+    use http::{header, StatusCode};
+
+    use super::MultiIssuerJwtAuthorizeError;
+
+    impl<Be, P> IntoResponse for MultiIssuerJwtAuthorizeError<Be, P>
+    where
+        Be: std::error::Error,
+        P: std::error::Error,
+    {
+        fn into_response(self) -> Response {
+            tracing::warn!(err = %self, "Unauthorized");
+
+            let status_code = match self {
+                MultiIssuerJwtAuthorizeError::Bearer(_) => StatusCode::UNAUTHORIZED,
+                MultiIssuerJwtAuthorizeError::Issuer(_) => StatusCode::UNAUTHORIZED,
+                MultiIssuerJwtAuthorizeError::JwkSet(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                MultiIssuerJwtAuthorizeError::Jwt(_) => StatusCode::UNAUTHORIZED,
+            };
+
+            (status_code, [(header::WWW_AUTHENTICATE, "Bearer")]).into_response()
+        }
+    }
+
+    impl<Be, P> From<MultiIssuerJwtAuthorizeError<Be, P>> for Response
+    where
+        Be: std::error::Error,
+        P: std::error::Error,
+    {
+        fn from(value: MultiIssuerJwtAuthorizeError<Be, P>) -> Self {
+            value.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use http::Request;
+    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+    use serde::Serialize;
+
+    use crate::authorize::{
+        header::bearer::DefaultBearerExtractor, jwt::jwk_set::StaticJwkSetProvider,
+    };
+
+    use super::*;
+
+    const ISSUER_A: &str = "https://issuer-a.com";
+    const ISSUER_B: &str = "https://issuer-b.com";
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct Claims {
+        iss: String,
+        exp: u64,
+    }
+
+    fn provider(secret: &[u8]) -> StaticJwkSetProvider {
+        StaticJwkSetProvider::empty()
+            .with_hmac_secret("key", secret, Algorithm::HS256)
+            .expect("Failed to create HMAC key")
+    }
+
+    fn parts(iss: &str, secret: &[u8]) -> Parts {
+        let exp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs()
+            + 60;
+
+        let header = Header {
+            kid: Some(String::from("key")),
+            ..Default::default()
+        };
+
+        let claims = Claims {
+            iss: iss.to_string(),
+            exp,
+        };
+
+        let jwt = encode(&header, &claims, &EncodingKey::from_secret(secret))
+            .expect("Failed to encode jwt");
+
+        Request::builder()
+            .header("authorization", format!("Bearer {jwt}"))
+            .body(())
+            .expect("Valid request")
+            .into_parts()
+            .0
+    }
+
+    #[tokio::test]
+    async fn tokens_are_routed_by_issuer() {
+        let authorizer = MultiIssuerJwtAuthorizerBuilder::new(DefaultBearerExtractor::new())
+            .issuer(ISSUER_A, provider(b"secret-a"), Validation::new())
+            .issuer(ISSUER_B, provider(b"secret-b"), Validation::new())
+            .build::<Claims>();
+
+        let claims = authorizer
+            .extract(&parts(ISSUER_A, b"secret-a"))
+            .await
+            .expect("Token from issuer a should be valid");
+
+        assert_eq!(claims.iss, ISSUER_A);
+
+        let claims = authorizer
+            .extract(&parts(ISSUER_B, b"secret-b"))
+            .await
+            .expect("Token from issuer b should be valid");
+
+        assert_eq!(claims.iss, ISSUER_B);
+
+        let result = authorizer.extract(&parts(ISSUER_B, b"secret-a")).await;
+
+        assert!(matches!(
+            result,
+            Err(MultiIssuerJwtAuthorizeError::Jwt(
+                DefaultJwtValidationError::DecodeData(_)
+            ))
+        ));
+
+        let result = authorizer
+            .extract(&parts("https://unknown.com", b"secret-a"))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(MultiIssuerJwtAuthorizeError::Issuer(
+                MultiIssuerError::UnknownIssuer { .. }
+            ))
+        ));
+    }
+}
@@ -2,19 +2,24 @@ use std::collections::HashSet;
 
 use jsonwebtoken::{Algorithm, Validation as JsonWebTokenValidation};
 
+use super::{claim_rule::ClaimRule, key_selection::KeySelection};
+
 /// Refer to the [`Validation`](jsonwebtoken::Validation) struct from the [`jsonwebtoken`] crate for more information.
 #[derive(Debug, Clone)]
 pub struct Validation {
-    required_spec_claims: HashSet<String>,
-    leeway: u64,
-    reject_tokens_expiring_in_less_than: u64,
-    validate_exp: bool,
-    validate_nbf: bool,
-    validate_aud: bool,
-    aud: Option<HashSet<String>>,
-    iss: Option<HashSet<String>>,
-    sub: Option<String>,
-    validate_signature: bool,
+    pub(crate) required_spec_claims: HashSet<String>,
+    pub(crate) leeway: u64,
+    pub(crate) reject_tokens_expiring_in_less_than: u64,
+    pub(crate) validate_exp: bool,
+    pub(crate) validate_nbf: bool,
+    pub(crate) validate_aud: bool,
+    pub(crate) aud: Option<HashSet<String>>,
+    pub(crate) iss: Option<HashSet<String>>,
+    pub(crate) sub: Option<String>,
+    pub(crate) validate_signature: bool,
+    pub(crate) key_selection: KeySelection,
+    pub(crate) algorithms: Option<HashSet<Algorithm>>,
+    pub(crate) claim_rules: Vec<ClaimRule>,
 }
 
 impl Default for Validation {
@@ -30,6 +35,9 @@ impl Default for Validation {
             iss: None,
             sub: None,
             validate_signature: true,
+            key_selection: KeySelection::Kid,
+            algorithms: None,
+            claim_rules: Vec::new(),
         }
     }
 }
@@ -93,6 +101,26 @@ impl Validation {
         self
     }
 
+    /// How the keys used to verify a JWT are selected from the JWK set. Defaults to [`KeySelection::Kid`].
+    pub fn key_selection(mut self, key_selection: KeySelection) -> Self {
+        self.key_selection = key_selection;
+        self
+    }
+
+    /// Only accept JWTs signed with one of the given algorithms. By default every algorithm that fits the key is accepted.
+    ///
+    /// Restricting this to the algorithms your issuer actually uses prevents algorithm confusion attacks.
+    pub fn algorithms(mut self, algorithms: &[Algorithm]) -> Self {
+        self.algorithms = Some(algorithms.iter().copied().collect());
+        self
+    }
+
+    /// Adds a [`ClaimRule`] that every JWT must satisfy. Rules are checked in the order they were added.
+    pub fn claim_rule(mut self, claim_rule: ClaimRule) -> Self {
+        self.claim_rules.push(claim_rule);
+        self
+    }
+
     pub fn to_jsonwebtoken_validation(&self, algorithm: Algorithm) -> JsonWebTokenValidation {
         let mut validation = JsonWebTokenValidation::new(algorithm);
 
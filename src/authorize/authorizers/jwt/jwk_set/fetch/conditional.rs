@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use http::{
+    header::{AGE, CACHE_CONTROL, ETAG, LAST_MODIFIED},
+    HeaderMap,
+};
+use jsonwebtoken::jwk::JwkSet;
+
+/// Caching information returned by the server along with a JWK set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheMetadata {
+    /// The remaining freshness lifetime from `Cache-Control: max-age`, minus `Age`.
+    pub max_age: Option<Duration>,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CacheMetadata {
+    /// Reads `Cache-Control`, `Age`, `ETag` and `Last-Modified` from the response headers.
+    ///
+    /// `no-cache` and `no-store` result in a `max_age` of zero.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+
+        let age = header(AGE)
+            .and_then(|age| age.trim().parse::<u64>().ok())
+            .unwrap_or(0);
+
+        let directives = headers
+            .get_all(CACHE_CONTROL)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .collect::<Vec<_>>();
+
+        let no_cache = directives.iter().any(|directive| {
+            directive.eq_ignore_ascii_case("no-cache") || directive.eq_ignore_ascii_case("no-store")
+        });
+
+        let max_age = if no_cache {
+            Some(0)
+        } else {
+            directives.iter().find_map(|directive| {
+                let (name, value) = directive.split_once('=')?;
+
+                if !name.trim().eq_ignore_ascii_case("max-age") {
+                    return None;
+                }
+
+                value.trim().trim_matches('"').parse::<u64>().ok()
+            })
+        };
+
+        Self {
+            max_age: max_age.map(|max_age| Duration::from_secs(max_age.saturating_sub(age))),
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ConditionalJwkSet {
+    Modified {
+        jwk_set: JwkSet,
+        cache: CacheMetadata,
+    },
+    /// The JWK set did not change since it was fetched with the given [`CacheMetadata`].
+    NotModified { cache: CacheMetadata },
+}
+
+impl ConditionalJwkSet {
+    pub fn cache(&self) -> &CacheMetadata {
+        match self {
+            ConditionalJwkSet::Modified { cache, .. } => cache,
+            ConditionalJwkSet::NotModified { cache } => cache,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn cache_metadata_from_headers() {
+        let mut headers = HeaderMap::new();
+
+        headers.insert(
+            CACHE_CONTROL,
+            HeaderValue::from_static("public, max-age=300, must-revalidate"),
+        );
+        headers.insert(AGE, HeaderValue::from_static("100"));
+        headers.insert(ETAG, HeaderValue::from_static("\"abc\""));
+
+        let cache = CacheMetadata::from_headers(&headers);
+
+        assert_eq!(cache.max_age, Some(Duration::from_secs(200)));
+        assert_eq!(cache.etag.as_deref(), Some("\"abc\""));
+        assert_eq!(cache.last_modified, None);
+
+        headers.append(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+
+        let cache = CacheMetadata::from_headers(&headers);
+
+        assert_eq!(cache.max_age, Some(Duration::ZERO));
+    }
+}
@@ -0,0 +1,174 @@
+use std::path::{Path, PathBuf};
+
+use jsonwebtoken::jwk::JwkSet;
+use sha2::{Digest, Sha256};
+
+use crate::authorize::jwt::jwk_set::fetch::{CacheMetadata, ConditionalJwkSet, JwkSetFetcher};
+
+/// Reads a JWK set from a JSON file.
+///
+/// Conditional fetches compare a SHA-256 hash of the file contents, used as the `etag`,
+/// so a rotating provider only parses the file again after it changed.
+#[derive(Debug, Clone)]
+pub struct FileJwkSetFetcher {
+    path: PathBuf,
+}
+
+impl FileJwkSetFetcher {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn read(&self) -> Result<Vec<u8>, FileJwkSetFetchError> {
+        tokio::fs::read(&self.path)
+            .await
+            .map_err(FileJwkSetFetchError::Read)
+    }
+
+    fn parse(contents: &[u8]) -> Result<JwkSet, FileJwkSetFetchError> {
+        serde_json::from_slice(contents).map_err(FileJwkSetFetchError::Parse)
+    }
+}
+
+impl JwkSetFetcher for FileJwkSetFetcher {
+    type Error = FileJwkSetFetchError;
+
+    async fn fetch_jwk_set(&self) -> Result<JwkSet, Self::Error> {
+        tracing::debug!(path = %self.path.display(), "Reading JWK set");
+
+        Self::parse(&self.read().await?)
+    }
+
+    async fn fetch_jwk_set_conditional(
+        &self,
+        cache: &CacheMetadata,
+    ) -> Result<ConditionalJwkSet, Self::Error> {
+        let contents = self.read().await?;
+
+        let fetched_cache = CacheMetadata {
+            etag: Some(hex::encode(Sha256::digest(&contents))),
+            ..Default::default()
+        };
+
+        if fetched_cache.etag == cache.etag {
+            tracing::debug!(path = %self.path.display(), "JWK set file not modified");
+
+            return Ok(ConditionalJwkSet::NotModified {
+                cache: fetched_cache,
+            });
+        }
+
+        tracing::debug!(path = %self.path.display(), "Parsing JWK set");
+
+        let jwk_set = Self::parse(&contents)?;
+
+        Ok(ConditionalJwkSet::Modified {
+            jwk_set,
+            cache: fetched_cache,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FileJwkSetFetchError {
+    #[error("Failed to read JWK set file: {0}")]
+    Read(#[source] std::io::Error),
+    #[error("Failed to parse JWK set: {0}")]
+    Parse(#[source] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use crate::authorize::jwt::jwk_set::{rotating::RotatingJwkSetProvider, JwkSetProvider};
+
+    use super::*;
+
+    fn jwk_set(kid: &str) -> String {
+        format!(r#"{{"keys":[{{"kty":"oct","kid":"{kid}","k":"c2VjcmV0"}}]}}"#)
+    }
+
+    #[tokio::test]
+    async fn unchanged_file_is_not_read_again() {
+        let path = std::env::temp_dir().join(format!("jwks-{}.json", std::process::id()));
+
+        tokio::fs::write(&path, r#"{"keys":[]}"#)
+            .await
+            .expect("Failed to write JWK set file");
+
+        let fetcher = FileJwkSetFetcher::new(&path);
+
+        let fetched = fetcher
+            .fetch_jwk_set_conditional(&CacheMetadata::default())
+            .await
+            .expect("Failed to fetch JWK set");
+
+        assert!(matches!(fetched, ConditionalJwkSet::Modified { .. }));
+
+        let fetched = fetcher
+            .fetch_jwk_set_conditional(fetched.cache())
+            .await
+            .expect("Failed to fetch JWK set");
+
+        assert!(matches!(fetched, ConditionalJwkSet::NotModified { .. }));
+
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+
+    #[tokio::test]
+    async fn rotated_file_is_picked_up_by_a_rotating_provider() {
+        let path = std::env::temp_dir().join(format!("jwks-rotated-{}.json", std::process::id()));
+
+        tokio::fs::write(&path, jwk_set("old"))
+            .await
+            .expect("Failed to write JWK set file");
+
+        let jwk_set_provider = RotatingJwkSetProvider::new(60, FileJwkSetFetcher::new(&path))
+            .await
+            .expect("Failed to create rotating jwk set provider");
+
+        assert!(jwk_set_provider
+            .provide_jwk_set()
+            .await
+            .unwrap()
+            .as_ref()
+            .find("new")
+            .is_none());
+
+        let modified = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .expect("Failed to read modification time");
+
+        tokio::fs::write(&path, jwk_set("new"))
+            .await
+            .expect("Failed to write JWK set file");
+
+        // Same length and modification time as before, so only the contents differ.
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(modified))
+            .expect("Failed to set modification time");
+
+        let refreshed = jwk_set_provider
+            .refresh_jwk_set_on_unknown_kid(Instant::now())
+            .await
+            .expect("Failed to refresh JWK set");
+
+        assert!(refreshed);
+        assert!(jwk_set_provider
+            .provide_jwk_set()
+            .await
+            .unwrap()
+            .as_ref()
+            .find("new")
+            .is_some());
+
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+}
@@ -1,6 +1,10 @@
+use http::{
+    header::{IF_MODIFIED_SINCE, IF_NONE_MATCH},
+    StatusCode,
+};
 use jsonwebtoken::jwk::JwkSet;
 
-use crate::authorize::jwt::jwk_set::fetch::JwkSetFetcher;
+use crate::authorize::jwt::jwk_set::fetch::{CacheMetadata, ConditionalJwkSet, JwkSetFetcher};
 
 #[derive(Debug)]
 pub struct HttpJwkSetFetcher {
@@ -29,12 +33,61 @@ impl JwkSetFetcher for HttpJwkSetFetcher {
             .send()
             .await
             .map_err(HttpJwkSetFetchError::Fetch)?
+            .error_for_status()
+            .map_err(HttpJwkSetFetchError::Fetch)?
             .json::<JwkSet>()
             .await
             .map_err(HttpJwkSetFetchError::Parse)?;
 
         Ok(jwks)
     }
+
+    async fn fetch_jwk_set_conditional(
+        &self,
+        cache: &CacheMetadata,
+    ) -> Result<ConditionalJwkSet, Self::Error> {
+        tracing::debug!(etag = ?cache.etag, last_modified = ?cache.last_modified, "Fetching JWK set");
+
+        let mut request = self.http_client.get(&self.jwks_uri);
+
+        if let Some(etag) = &cache.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+
+        if let Some(last_modified) = &cache.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+
+        let response = request.send().await.map_err(HttpJwkSetFetchError::Fetch)?;
+
+        let mut fetched_cache = CacheMetadata::from_headers(response.headers());
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            tracing::debug!("JWK set not modified");
+
+            // A 304 response is not required to repeat the validators.
+            fetched_cache.etag = fetched_cache.etag.or_else(|| cache.etag.clone());
+            fetched_cache.last_modified = fetched_cache
+                .last_modified
+                .or_else(|| cache.last_modified.clone());
+
+            return Ok(ConditionalJwkSet::NotModified {
+                cache: fetched_cache,
+            });
+        }
+
+        let jwk_set = response
+            .error_for_status()
+            .map_err(HttpJwkSetFetchError::Fetch)?
+            .json::<JwkSet>()
+            .await
+            .map_err(HttpJwkSetFetchError::Parse)?;
+
+        Ok(ConditionalJwkSet::Modified {
+            jwk_set,
+            cache: fetched_cache,
+        })
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
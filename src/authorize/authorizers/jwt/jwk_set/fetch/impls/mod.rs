@@ -1 +1,3 @@
+pub mod file_jwk_set_fetcher;
 pub mod http_jwk_set_fetcher;
+pub mod oidc_discovery_jwk_set_fetcher;
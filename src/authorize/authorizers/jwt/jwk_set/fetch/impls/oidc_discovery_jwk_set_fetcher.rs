@@ -0,0 +1,519 @@
+use std::{
+    str::FromStr,
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+
+use jsonwebtoken::{jwk::JwkSet, Algorithm};
+use serde::Deserialize;
+
+use crate::authorize::jwt::{
+    jwk_set::fetch::{CacheMetadata, ConditionalJwkSet, JwkSetFetcher},
+    Validation,
+};
+
+use super::http_jwk_set_fetcher::{HttpJwkSetFetchError, HttpJwkSetFetcher};
+
+/// The subset of the [OpenID Provider Metadata](https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderMetadata)
+/// that is relevant for validating JWTs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OidcProviderMetadata {
+    pub issuer: String,
+    pub jwks_uri: String,
+    #[serde(default)]
+    pub id_token_signing_alg_values_supported: Vec<String>,
+}
+
+impl OidcProviderMetadata {
+    /// Supported signing algorithms. Algorithms unknown to [`jsonwebtoken`] are skipped.
+    pub fn algorithms(&self) -> Vec<Algorithm> {
+        self.id_token_signing_alg_values_supported
+            .iter()
+            .filter_map(|alg| Algorithm::from_str(alg).ok())
+            .collect()
+    }
+
+    /// A [`Validation`] with the issuer set to the discovered issuer
+    /// and the allowed algorithms set to the supported signing algorithms, if any were discovered.
+    pub fn validation(&self) -> Validation {
+        let validation = Validation::new().iss(&[&self.issuer]);
+
+        let algorithms = self.algorithms();
+
+        if algorithms.is_empty() {
+            return validation;
+        }
+
+        validation.algorithms(&algorithms)
+    }
+}
+
+/// Fetches the JWK set from the `jwks_uri` found in the issuer's `/.well-known/openid-configuration`.
+///
+/// Discovery runs on creation, before a fetch once the rediscovery interval has passed,
+/// and whenever fetching the JWK set fails, so a changed `jwks_uri` is picked up
+/// even if the old one still serves a stale JWK set.
+#[derive(Debug)]
+pub struct OidcDiscoveryJwkSetFetcher {
+    issuer: String,
+    http_client: reqwest::Client,
+    rediscovery_interval: Duration,
+    metadata: RwLock<(OidcProviderMetadata, Instant)>,
+}
+
+impl OidcDiscoveryJwkSetFetcher {
+    pub async fn new(
+        issuer: String,
+        http_client: reqwest::Client,
+    ) -> Result<Self, OidcDiscoveryJwkSetFetchError> {
+        let metadata = Self::discover_inner(&issuer, &http_client).await?;
+
+        Ok(Self {
+            issuer,
+            http_client,
+            rediscovery_interval: Duration::from_secs(60 * 60),
+            metadata: RwLock::new((metadata, Instant::now())),
+        })
+    }
+
+    /// Time after which discovery runs again before the next fetch. Defaults to one hour.
+    pub fn rediscovery_interval_in_seconds(mut self, rediscovery_interval_in_seconds: u64) -> Self {
+        self.rediscovery_interval = Duration::from_secs(rediscovery_interval_in_seconds);
+        self
+    }
+
+    fn discovery_uri(issuer: &str) -> String {
+        format!(
+            "{}/.well-known/openid-configuration",
+            issuer.trim_end_matches('/')
+        )
+    }
+
+    async fn discover_inner(
+        issuer: &str,
+        http_client: &reqwest::Client,
+    ) -> Result<OidcProviderMetadata, OidcDiscoveryJwkSetFetchError> {
+        tracing::debug!(%issuer, "Discovering OpenID provider metadata");
+
+        let metadata = http_client
+            .get(Self::discovery_uri(issuer))
+            .send()
+            .await
+            .map_err(OidcDiscoveryJwkSetFetchError::Discover)?
+            .error_for_status()
+            .map_err(OidcDiscoveryJwkSetFetchError::Discover)?
+            .json::<OidcProviderMetadata>()
+            .await
+            .map_err(OidcDiscoveryJwkSetFetchError::ParseMetadata)?;
+
+        if metadata.issuer.trim_end_matches('/') != issuer.trim_end_matches('/') {
+            return Err(OidcDiscoveryJwkSetFetchError::IssuerMismatch {
+                expected: issuer.to_string(),
+                discovered: metadata.issuer,
+            });
+        }
+
+        Ok(metadata)
+    }
+
+    /// Runs discovery again and stores the result.
+    pub async fn discover(&self) -> Result<OidcProviderMetadata, OidcDiscoveryJwkSetFetchError> {
+        let metadata = Self::discover_inner(&self.issuer, &self.http_client).await?;
+
+        *self.metadata.write().unwrap_or_else(|err| err.into_inner()) =
+            (metadata.clone(), Instant::now());
+
+        Ok(metadata)
+    }
+
+    pub fn metadata(&self) -> OidcProviderMetadata {
+        self.metadata
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .0
+            .clone()
+    }
+
+    /// The current `jwks_uri`, after running discovery again if the rediscovery interval has passed.
+    ///
+    /// If discovery fails, the previous `jwks_uri` is kept.
+    async fn jwks_uri(&self) -> String {
+        let (jwks_uri, discovered_at) = {
+            let metadata = self.metadata.read().unwrap_or_else(|err| err.into_inner());
+
+            (metadata.0.jwks_uri.clone(), metadata.1)
+        };
+
+        if discovered_at.elapsed() < self.rediscovery_interval {
+            return jwks_uri;
+        }
+
+        match self.discover().await {
+            Ok(metadata) => metadata.jwks_uri,
+            Err(err) => {
+                tracing::warn!(%err, "Failed to run discovery again. Keeping the previous JWKS uri");
+
+                jwks_uri
+            }
+        }
+    }
+
+    pub fn issuer(&self) -> String {
+        self.metadata().issuer
+    }
+
+    pub fn algorithms(&self) -> Vec<Algorithm> {
+        self.metadata().algorithms()
+    }
+
+    pub fn validation(&self) -> Validation {
+        self.metadata().validation()
+    }
+
+    fn jwk_set_fetcher(&self, jwks_uri: String) -> HttpJwkSetFetcher {
+        HttpJwkSetFetcher::new(jwks_uri, self.http_client.clone())
+    }
+
+    /// Runs discovery after fetching from `jwks_uri` failed and returns the new `jwks_uri`,
+    /// or the fetch error if it did not change.
+    async fn rediscover(
+        &self,
+        jwks_uri: &str,
+        err: HttpJwkSetFetchError,
+    ) -> Result<String, OidcDiscoveryJwkSetFetchError> {
+        tracing::warn!(?err, "Failed to fetch JWK set. Running discovery again");
+
+        let metadata = self.discover().await?;
+
+        if metadata.jwks_uri == jwks_uri {
+            return Err(OidcDiscoveryJwkSetFetchError::Fetch(err));
+        }
+
+        tracing::info!(old = %jwks_uri, new = %metadata.jwks_uri, "JWKS uri changed");
+
+        Ok(metadata.jwks_uri)
+    }
+}
+
+impl JwkSetFetcher for OidcDiscoveryJwkSetFetcher {
+    type Error = OidcDiscoveryJwkSetFetchError;
+
+    async fn fetch_jwk_set(&self) -> Result<JwkSet, Self::Error> {
+        let jwks_uri = self.jwks_uri().await;
+
+        let err = match self.jwk_set_fetcher(jwks_uri.clone()).fetch_jwk_set().await {
+            Ok(jwks) => return Ok(jwks),
+            Err(err) => err,
+        };
+
+        let jwks_uri = self.rediscover(&jwks_uri, err).await?;
+
+        self.jwk_set_fetcher(jwks_uri)
+            .fetch_jwk_set()
+            .await
+            .map_err(OidcDiscoveryJwkSetFetchError::Fetch)
+    }
+
+    async fn fetch_jwk_set_conditional(
+        &self,
+        cache: &CacheMetadata,
+    ) -> Result<ConditionalJwkSet, Self::Error> {
+        let previous_jwks_uri = self.metadata().jwks_uri;
+        let jwks_uri = self.jwks_uri().await;
+
+        // The cache metadata belongs to the previous uri.
+        let default_cache = CacheMetadata::default();
+        let cache = if jwks_uri == previous_jwks_uri {
+            cache
+        } else {
+            &default_cache
+        };
+
+        let err = match self
+            .jwk_set_fetcher(jwks_uri.clone())
+            .fetch_jwk_set_conditional(cache)
+            .await
+        {
+            Ok(jwks) => return Ok(jwks),
+            Err(err) => err,
+        };
+
+        let jwks_uri = self.rediscover(&jwks_uri, err).await?;
+
+        // The cache metadata belongs to the old uri.
+        self.jwk_set_fetcher(jwks_uri)
+            .fetch_jwk_set_conditional(&CacheMetadata::default())
+            .await
+            .map_err(OidcDiscoveryJwkSetFetchError::Fetch)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum OidcDiscoveryJwkSetFetchError {
+    #[error("Failed to fetch OpenID provider metadata: {0}")]
+    Discover(#[source] reqwest::Error),
+    #[error("Failed to parse OpenID provider metadata: {0}")]
+    ParseMetadata(#[source] reqwest::Error),
+    #[error("Discovered issuer {discovered} does not match the expected issuer {expected}")]
+    IssuerMismatch {
+        expected: String,
+        discovered: String,
+    },
+    #[error("Failed to fetch JWK set: {0}")]
+    Fetch(#[source] HttpJwkSetFetchError),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct Route {
+        status: u16,
+        etag: Option<&'static str>,
+        body: String,
+    }
+
+    /// Serves fixed responses by path and counts the requests to each path.
+    #[derive(Clone, Default)]
+    struct Server {
+        routes: Arc<Mutex<HashMap<String, Route>>>,
+        hits: Arc<Mutex<HashMap<String, usize>>>,
+    }
+
+    impl Server {
+        async fn start() -> (Self, String) {
+            let server = Self::default();
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("Bind");
+            let base = format!("http://{}", listener.local_addr().expect("Address"));
+
+            let handler = server.clone();
+
+            tokio::spawn(async move {
+                while let Ok((mut stream, _)) = listener.accept().await {
+                    let handler = handler.clone();
+
+                    tokio::spawn(async move {
+                        let mut request = Vec::new();
+                        let mut buf = [0; 1024];
+
+                        while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                            match stream.read(&mut buf).await {
+                                Ok(0) | Err(_) => return,
+                                Ok(n) => request.extend_from_slice(&buf[..n]),
+                            }
+                        }
+
+                        let response = handler.respond(&String::from_utf8_lossy(&request));
+                        let _ = stream.write_all(response.as_bytes()).await;
+                    });
+                }
+            });
+
+            (server, base)
+        }
+
+        fn route(&self, path: &str, status: u16, etag: Option<&'static str>, body: String) {
+            self.routes
+                .lock()
+                .unwrap()
+                .insert(path.to_string(), Route { status, etag, body });
+        }
+
+        fn hits(&self, path: &str) -> usize {
+            self.hits.lock().unwrap().get(path).copied().unwrap_or(0)
+        }
+
+        fn respond(&self, request: &str) -> String {
+            let path = request.split(' ').nth(1).unwrap_or_default().to_string();
+
+            let if_none_match = request.lines().find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+
+                name.eq_ignore_ascii_case("if-none-match")
+                    .then(|| value.trim().to_string())
+            });
+
+            *self.hits.lock().unwrap().entry(path.clone()).or_default() += 1;
+
+            let route = self.routes.lock().unwrap().get(&path).cloned();
+
+            let Route { status, etag, body } = route.unwrap_or(Route {
+                status: 404,
+                etag: None,
+                body: String::new(),
+            });
+
+            let (status, body) = match (etag, if_none_match) {
+                (Some(etag), Some(if_none_match)) if etag == if_none_match => (304, String::new()),
+                _ => (status, body),
+            };
+
+            let etag = etag
+                .map(|etag| format!("etag: {etag}\r\n"))
+                .unwrap_or_default();
+
+            format!(
+                "HTTP/1.1 {status} Status\r\ncontent-type: application/json\r\ncontent-length: {}\r\n{etag}connection: close\r\n\r\n{body}",
+                body.len()
+            )
+        }
+
+        fn discovery(&self, issuer: &str, jwks_path: &str) {
+            self.route(
+                "/.well-known/openid-configuration",
+                200,
+                None,
+                format!(
+                    r#"{{"issuer":"{issuer}","jwks_uri":"{issuer}{jwks_path}","id_token_signing_alg_values_supported":["RS256","unknown"]}}"#
+                ),
+            );
+        }
+    }
+
+    fn jwk_set(kid: &str) -> String {
+        format!(r#"{{"keys":[{{"kty":"oct","kid":"{kid}","k":"c2VjcmV0"}}]}}"#)
+    }
+
+    #[tokio::test]
+    async fn discovers_metadata_and_fetches_jwk_set() {
+        let (server, base) = Server::start().await;
+
+        server.discovery(&base, "/jwks");
+        server.route("/jwks", 200, None, jwk_set("key-1"));
+
+        let fetcher = OidcDiscoveryJwkSetFetcher::new(base.clone(), reqwest::Client::new())
+            .await
+            .expect("Discovery should succeed");
+
+        assert_eq!(fetcher.issuer(), base);
+        assert_eq!(fetcher.algorithms(), vec![Algorithm::RS256]);
+
+        let jwk_set = fetcher.fetch_jwk_set().await.expect("Fetch should succeed");
+
+        assert!(jwk_set.find("key-1").is_some());
+    }
+
+    #[tokio::test]
+    async fn issuer_mismatch_is_rejected() {
+        let (server, base) = Server::start().await;
+
+        server.discovery("https://other.example.com", "/jwks");
+
+        let result = OidcDiscoveryJwkSetFetcher::new(base, reqwest::Client::new()).await;
+
+        assert!(matches!(
+            result,
+            Err(OidcDiscoveryJwkSetFetchError::IssuerMismatch { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn failed_fetch_runs_discovery_again() {
+        let (server, base) = Server::start().await;
+
+        server.discovery(&base, "/old");
+        server.route("/old", 200, None, jwk_set("key-1"));
+
+        let fetcher = OidcDiscoveryJwkSetFetcher::new(base.clone(), reqwest::Client::new())
+            .await
+            .expect("Discovery should succeed");
+
+        server.route("/old", 404, None, String::new());
+        server.route("/new", 200, None, jwk_set("key-2"));
+        server.discovery(&base, "/new");
+
+        let jwk_set = fetcher.fetch_jwk_set().await.expect("Fetch should succeed");
+
+        assert!(jwk_set.find("key-2").is_some());
+        assert_eq!(fetcher.metadata().jwks_uri, format!("{base}/new"));
+        assert_eq!(server.hits("/.well-known/openid-configuration"), 2);
+
+        server.route("/new", 404, None, String::new());
+
+        assert!(matches!(
+            fetcher.fetch_jwk_set().await,
+            Err(OidcDiscoveryJwkSetFetchError::Fetch(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn conditional_fetch_is_delegated() {
+        let (server, base) = Server::start().await;
+
+        server.discovery(&base, "/jwks");
+        server.route("/jwks", 200, Some("\"v1\""), jwk_set("key-1"));
+
+        let fetcher = OidcDiscoveryJwkSetFetcher::new(base, reqwest::Client::new())
+            .await
+            .expect("Discovery should succeed");
+
+        let fetched = fetcher
+            .fetch_jwk_set_conditional(&CacheMetadata::default())
+            .await
+            .expect("Fetch should succeed");
+
+        let ConditionalJwkSet::Modified { cache, .. } = fetched else {
+            panic!("Expected a modified JWK set");
+        };
+
+        assert_eq!(cache.etag.as_deref(), Some("\"v1\""));
+
+        let fetched = fetcher
+            .fetch_jwk_set_conditional(&cache)
+            .await
+            .expect("Fetch should succeed");
+
+        assert!(matches!(fetched, ConditionalJwkSet::NotModified { .. }));
+        assert_eq!(server.hits("/jwks"), 2);
+    }
+
+    #[tokio::test]
+    async fn moved_jwks_uri_is_picked_up_after_the_rediscovery_interval() {
+        let (server, base) = Server::start().await;
+
+        server.discovery(&base, "/jwks-1");
+        server.route("/jwks-1", 200, Some("\"v1\""), jwk_set("key-1"));
+        server.route("/jwks-2", 200, Some("\"v1\""), jwk_set("key-2"));
+
+        let fetcher = OidcDiscoveryJwkSetFetcher::new(base.clone(), reqwest::Client::new())
+            .await
+            .expect("Discovery should succeed")
+            .rediscovery_interval_in_seconds(0);
+
+        let fetched = fetcher
+            .fetch_jwk_set_conditional(&CacheMetadata::default())
+            .await
+            .expect("Fetch should succeed");
+
+        assert!(matches!(
+            &fetched,
+            ConditionalJwkSet::Modified { jwk_set, .. } if jwk_set.find("key-1").is_some()
+        ));
+
+        // The old uri keeps serving the stale JWK set.
+        server.discovery(&base, "/jwks-2");
+
+        let fetched = fetcher
+            .fetch_jwk_set_conditional(fetched.cache())
+            .await
+            .expect("Fetch should succeed");
+
+        assert!(matches!(
+            &fetched,
+            ConditionalJwkSet::Modified { jwk_set, .. } if jwk_set.find("key-2").is_some()
+        ));
+
+        assert_eq!(server.hits("/jwks-2"), 1);
+    }
+}
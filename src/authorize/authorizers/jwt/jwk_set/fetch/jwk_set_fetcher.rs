@@ -2,11 +2,40 @@ use std::future::Future;
 
 use jsonwebtoken::jwk::JwkSet;
 
-#[cfg_attr(test, mockall::automock(type Error=anyhow::Error;))]
+use super::conditional::{CacheMetadata, ConditionalJwkSet};
+
 pub trait JwkSetFetcher {
     type Error;
 
     fn fetch_jwk_set(&self) -> impl Future<Output = Result<JwkSet, Self::Error>> + Send;
+
+    /// Fetches the JWK set unless it did not change since it was fetched with the given [`CacheMetadata`].
+    ///
+    /// The default implementation always fetches the JWK set and returns no caching information.
+    fn fetch_jwk_set_conditional(
+        &self,
+        _cache: &CacheMetadata,
+    ) -> impl Future<Output = Result<ConditionalJwkSet, Self::Error>> + Send {
+        let fetch = self.fetch_jwk_set();
+
+        async move {
+            fetch.await.map(|jwk_set| ConditionalJwkSet::Modified {
+                jwk_set,
+                cache: CacheMetadata::default(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mockall::mock! {
+    pub JwkSetFetcher {}
+
+    impl JwkSetFetcher for JwkSetFetcher {
+        type Error = anyhow::Error;
+
+        fn fetch_jwk_set(&self) -> impl Future<Output = Result<JwkSet, anyhow::Error>> + Send;
+    }
 }
 
 pub trait JwkSetFetcherExt: Sized + JwkSetFetcher {
@@ -47,4 +76,14 @@ where
             .await
             .map_err(|err| (self.map_err.clone())(err))
     }
+
+    async fn fetch_jwk_set_conditional(
+        &self,
+        cache: &CacheMetadata,
+    ) -> Result<ConditionalJwkSet, Self::Error> {
+        self.inner
+            .fetch_jwk_set_conditional(cache)
+            .await
+            .map_err(|err| (self.map_err.clone())(err))
+    }
 }
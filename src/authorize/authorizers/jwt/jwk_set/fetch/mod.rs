@@ -1,7 +1,15 @@
+mod conditional;
 mod impls;
 mod jwk_set_fetcher;
 
-pub use impls::http_jwk_set_fetcher::{HttpJwkSetFetchError, HttpJwkSetFetcher};
+pub use conditional::{CacheMetadata, ConditionalJwkSet};
+pub use impls::{
+    file_jwk_set_fetcher::{FileJwkSetFetchError, FileJwkSetFetcher},
+    http_jwk_set_fetcher::{HttpJwkSetFetchError, HttpJwkSetFetcher},
+    oidc_discovery_jwk_set_fetcher::{
+        OidcDiscoveryJwkSetFetchError, OidcDiscoveryJwkSetFetcher, OidcProviderMetadata,
+    },
+};
 pub use jwk_set_fetcher::{JwkSetFetcher, JwkSetFetcherExt, MapError};
 
 #[cfg(test)]
@@ -1 +1,2 @@
 pub mod rotating;
+pub mod static_jwk_set_provider;
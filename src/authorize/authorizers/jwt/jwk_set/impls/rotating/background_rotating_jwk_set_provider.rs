@@ -1,12 +1,45 @@
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use jsonwebtoken::jwk::JwkSet;
 use tokio::sync::{oneshot, RwLock, RwLockReadGuard};
 
 use crate::authorize::{
-    authorizers::jwt::jwk_set::jwk_set_provider::JwkSetProvider, jwt::jwk_set::fetch::JwkSetFetcher,
+    authorizers::jwt::jwk_set::jwk_set_provider::JwkSetProvider,
+    jwt::jwk_set::fetch::{CacheMetadata, ConditionalJwkSet, JwkSetFetcher},
 };
 
+use super::{cache_bounds::CacheBounds, forced_refresh::ForcedRefresh, stale_policy::StalePolicy};
+
+/// Options for [`BackgroundRotatingJwkSetProvider::new_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct BackgroundRotatingOptions {
+    stale_policy: StalePolicy,
+    cache_bounds: Option<CacheBounds>,
+}
+
+impl BackgroundRotatingOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// What to do when a background refresh fails. Defaults to [`StalePolicy::FailFast`].
+    pub fn stale_policy(mut self, stale_policy: StalePolicy) -> Self {
+        self.stale_policy = stale_policy;
+        self
+    }
+
+    /// Schedules the next refresh from the `Cache-Control: max-age` of the JWK set response, clamped to the given bounds.
+    ///
+    /// `refresh_interval_in_seconds` is still used if the response has no `max-age`.
+    pub fn cache_bounds(mut self, cache_bounds: CacheBounds) -> Self {
+        self.cache_bounds = Some(cache_bounds);
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct JwkSetHolder<F>
 where
@@ -14,7 +47,9 @@ where
     F::Error: std::error::Error + Clone + Send + Sync + 'static,
 {
     last_updated: Instant,
+    last_succeeded: Instant,
     last_error: Option<BackgroundRotatingJwkSetProvideError<F::Error>>,
+    cache: CacheMetadata,
     jwk_set: JwkSet,
 }
 
@@ -26,6 +61,8 @@ where
 {
     holder: Arc<RwLock<JwkSetHolder<F>>>,
     jwk_set_fetcher: Arc<F>,
+    stale_policy: StalePolicy,
+    forced_refresh: ForcedRefresh,
     _cancellation_tx: oneshot::Sender<()>,
 }
 
@@ -38,16 +75,59 @@ where
         refresh_interval_in_seconds: u64,
         jwk_set_fetcher: F,
     ) -> Result<Self, BackgroundRotatingJwkSetProvideError<F::Error>> {
-        let jwk_set = jwk_set_fetcher
-            .fetch_jwk_set()
+        Self::new_with_options(
+            refresh_interval_in_seconds,
+            jwk_set_fetcher,
+            BackgroundRotatingOptions::default(),
+        )
+        .await
+    }
+
+    pub async fn new_with_stale_policy(
+        refresh_interval_in_seconds: u64,
+        jwk_set_fetcher: F,
+        stale_policy: StalePolicy,
+    ) -> Result<Self, BackgroundRotatingJwkSetProvideError<F::Error>> {
+        Self::new_with_options(
+            refresh_interval_in_seconds,
+            jwk_set_fetcher,
+            BackgroundRotatingOptions::default().stale_policy(stale_policy),
+        )
+        .await
+    }
+
+    pub async fn new_with_options(
+        refresh_interval_in_seconds: u64,
+        jwk_set_fetcher: F,
+        options: BackgroundRotatingOptions,
+    ) -> Result<Self, BackgroundRotatingJwkSetProvideError<F::Error>> {
+        let BackgroundRotatingOptions {
+            stale_policy,
+            cache_bounds,
+        } = options;
+
+        let (jwk_set, cache) = match jwk_set_fetcher
+            .fetch_jwk_set_conditional(&CacheMetadata::default())
             .await
-            .map_err(BackgroundRotatingJwkSetProvideError::Fetch)?;
+            .map_err(BackgroundRotatingJwkSetProvideError::Fetch)?
+        {
+            ConditionalJwkSet::Modified { jwk_set, cache } => (jwk_set, cache),
+            ConditionalJwkSet::NotModified { cache } => (
+                jwk_set_fetcher
+                    .fetch_jwk_set()
+                    .await
+                    .map_err(BackgroundRotatingJwkSetProvideError::Fetch)?,
+                cache,
+            ),
+        };
 
         let last_updated = Instant::now();
 
         let holder = Arc::new(RwLock::new(JwkSetHolder {
             last_updated,
+            last_succeeded: last_updated,
             last_error: None,
+            cache,
             jwk_set,
         }));
 
@@ -57,6 +137,8 @@ where
 
         tokio::spawn(Self::background_refresh_loop(
             refresh_interval_in_seconds,
+            stale_policy.clone(),
+            cache_bounds,
             jwk_set_fetcher.clone(),
             holder.clone(),
             rx,
@@ -65,23 +147,58 @@ where
         Ok(Self {
             holder,
             jwk_set_fetcher,
+            stale_policy,
+            forced_refresh: ForcedRefresh::new(30),
             _cancellation_tx: tx,
         })
     }
 
+    /// Minimum time between two refreshes triggered by tokens with an unknown `kid`. Defaults to 30 seconds.
+    pub fn min_forced_refresh_interval_in_seconds(
+        mut self,
+        min_forced_refresh_interval_in_seconds: u64,
+    ) -> Self {
+        self.forced_refresh
+            .set_min_interval_in_seconds(min_forced_refresh_interval_in_seconds);
+        self
+    }
+
     async fn background_refresh_loop(
         refresh_interval_in_seconds: u64,
+        stale_policy: StalePolicy,
+        cache_bounds: Option<CacheBounds>,
         jwk_set_fetcher: Arc<F>,
         holder: Arc<RwLock<JwkSetHolder<F>>>,
         mut cancellation_rx: oneshot::Receiver<()>,
     ) {
+        let refresh_interval = Duration::from_secs(refresh_interval_in_seconds);
+
+        let time_to_live = |cache: &CacheMetadata| match &cache_bounds {
+            Some(cache_bounds) => cache_bounds.time_to_live(cache, refresh_interval),
+            None => refresh_interval,
+        };
+
+        let mut next_refresh = time_to_live(&holder.read().await.cache);
+        let mut failed_attempts = 0;
+
         loop {
-            tracing::debug!("Next refresh in {} seconds", refresh_interval_in_seconds);
+            tracing::debug!("Next refresh in {:?}", next_refresh);
 
             tokio::select! {
-                _ = tokio::time::sleep(tokio::time::Duration::from_secs(refresh_interval_in_seconds)) => {
-                    if let Err(err) = Self::refresh_jwk_set_inner(&jwk_set_fetcher, &holder).await {
-                        tracing::error!(?err, "Failed to refresh JWK set");
+                _ = tokio::time::sleep(next_refresh) => {
+                    match Self::refresh_jwk_set_inner(&jwk_set_fetcher, &holder).await {
+                        Ok(_) => {
+                            failed_attempts = 0;
+                            next_refresh = time_to_live(&holder.read().await.cache);
+                        }
+                        Err(err) => {
+                            tracing::error!(?err, "Failed to refresh JWK set");
+
+                            if let StalePolicy::ServeStale { backoff, .. } = &stale_policy {
+                                next_refresh = backoff.delay(failed_attempts).min(refresh_interval);
+                                failed_attempts = failed_attempts.saturating_add(1);
+                            }
+                        }
                     }
                 }
                 _ = &mut cancellation_rx => {
@@ -99,17 +216,31 @@ where
     ) -> Result<impl AsRef<JwkSet> + 'a, BackgroundRotatingJwkSetProvideError<F::Error>> {
         tracing::debug!("Refreshing JWK set");
 
-        let jwk_set = jwk_set_fetcher.fetch_jwk_set().await;
+        let cache = holder.read().await.cache.clone();
+
+        let fetched = jwk_set_fetcher.fetch_jwk_set_conditional(&cache).await;
 
         let last_updated = Instant::now();
 
-        match jwk_set {
-            Ok(jwk_set) => {
-                *(holder.write().await) = JwkSetHolder {
-                    last_updated,
-                    last_error: None,
-                    jwk_set,
-                };
+        match fetched {
+            Ok(fetched) => {
+                {
+                    let mut holder = holder.write().await;
+
+                    holder.last_updated = last_updated;
+                    holder.last_succeeded = last_updated;
+                    holder.last_error = None;
+
+                    match fetched {
+                        ConditionalJwkSet::Modified { jwk_set, cache } => {
+                            holder.cache = cache;
+                            holder.jwk_set = jwk_set;
+                        }
+                        ConditionalJwkSet::NotModified { cache } => {
+                            holder.cache = cache;
+                        }
+                    }
+                }
 
                 let guard = holder.read().await;
 
@@ -140,6 +271,10 @@ where
     pub async fn last_updated(&self) -> Instant {
         self.get().read().await.last_updated
     }
+
+    pub async fn last_succeeded(&self) -> Instant {
+        self.get().read().await.last_succeeded
+    }
 }
 
 impl<F> JwkSetProvider for BackgroundRotatingJwkSetProvider<F>
@@ -153,11 +288,35 @@ where
         let guard = self.get().read().await;
 
         if let Some(err) = &guard.last_error {
-            return Err(err.clone());
+            match &self.stale_policy {
+                StalePolicy::FailFast => return Err(err.clone()),
+                StalePolicy::ServeStale {
+                    max_stale_in_seconds,
+                    ..
+                } => {
+                    let stale_for = guard.last_succeeded.elapsed();
+
+                    if stale_for > Duration::from_secs(*max_stale_in_seconds) {
+                        return Err(err.clone());
+                    }
+
+                    tracing::warn!(?stale_for, "Serving stale JWK set");
+                }
+            }
         }
 
         Ok(JwkSetReadGuard::new(guard))
     }
+
+    async fn refresh_jwk_set_on_unknown_kid(&self, read_at: Instant) -> Result<bool, Self::Error> {
+        self.forced_refresh
+            .run(
+                read_at,
+                || self.last_succeeded(),
+                || async { self.refresh_jwk_set().await.map(|_| ()) },
+            )
+            .await
+    }
 }
 
 impl<F> AsRef<JwkSet> for JwkSetHolder<F>
@@ -212,7 +371,10 @@ mod tests {
     };
 
     use crate::{
-        authorize::jwt::jwk_set::fetch::{JwkSetFetcherExt, MockJwkSetFetcher},
+        authorize::jwt::jwk_set::{
+            fetch::{JwkSetFetcherExt, MockJwkSetFetcher},
+            rotating::Backoff,
+        },
         test::init_tracing,
     };
 
@@ -321,4 +483,58 @@ mod tests {
             panic!("Expected error")
         }
     }
+
+    #[tokio::test]
+    async fn stale_jwk_set_is_served_until_max_stale() {
+        init_tracing();
+
+        let mut jwk_set_fetcher = MockJwkSetFetcher::default();
+
+        jwk_set_fetcher
+            .expect_fetch_jwk_set()
+            .times(1)
+            .returning(|| Box::pin(async { Ok(JwkSet { keys: Vec::new() }) }));
+
+        jwk_set_fetcher
+            .expect_fetch_jwk_set()
+            .returning(|| Box::pin(async { bail!("Oh") }));
+
+        let jwk_set_fetcher = jwk_set_fetcher.map_err(|_| MockJwkSetFetcherError);
+
+        let stale_policy = StalePolicy::ServeStale {
+            max_stale_in_seconds: 2,
+            backoff: Backoff::default()
+                .initial_delay(Duration::from_millis(100))
+                .max_delay(Duration::from_millis(200))
+                .jitter(0.5),
+        };
+
+        let background_rotating_jwk_set_provider =
+            BackgroundRotatingJwkSetProvider::new_with_stale_policy(
+                1,
+                jwk_set_fetcher,
+                stale_policy,
+            )
+            .await
+            .expect("Failed to create background rotating jwk set provider");
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+
+        // Don't hold on to the read guard, the background refresh needs to write.
+        let is_served = background_rotating_jwk_set_provider
+            .provide_jwk_set()
+            .await
+            .is_ok();
+
+        assert!(is_served);
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        let is_served = background_rotating_jwk_set_provider
+            .provide_jwk_set()
+            .await
+            .is_ok();
+
+        assert!(!is_served);
+    }
 }
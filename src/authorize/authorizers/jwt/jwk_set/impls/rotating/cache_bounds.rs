@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use crate::authorize::jwt::jwk_set::fetch::CacheMetadata;
+
+/// Bounds for refresh intervals taken from the `Cache-Control: max-age` of a JWK set response.
+///
+/// Keeps a misconfigured server from making the provider refresh on every request or never again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheBounds {
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl CacheBounds {
+    pub const fn new_in_seconds(min_in_seconds: u64, max_in_seconds: u64) -> Self {
+        Self {
+            min: Duration::from_secs(min_in_seconds),
+            max: Duration::from_secs(max_in_seconds),
+        }
+    }
+
+    /// Returns the `max_age` of the given [`CacheMetadata`] clamped to these bounds,
+    /// or `default` if the server did not send one.
+    pub fn time_to_live(&self, cache: &CacheMetadata, default: Duration) -> Duration {
+        match cache.max_age {
+            Some(max_age) => max_age.max(self.min).min(self.max),
+            None => default,
+        }
+    }
+}
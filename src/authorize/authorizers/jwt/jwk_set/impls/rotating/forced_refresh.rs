@@ -0,0 +1,63 @@
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::Mutex;
+
+/// Limits how often tokens with an unknown `kid` trigger a refresh.
+#[derive(Debug)]
+pub(super) struct ForcedRefresh {
+    min_interval: Duration,
+    last_forced_refresh: Mutex<Option<Instant>>,
+}
+
+impl ForcedRefresh {
+    pub(super) fn new(min_interval_in_seconds: u64) -> Self {
+        Self {
+            min_interval: Duration::from_secs(min_interval_in_seconds),
+            last_forced_refresh: Mutex::new(None),
+        }
+    }
+
+    pub(super) fn set_min_interval_in_seconds(&mut self, min_interval_in_seconds: u64) {
+        self.min_interval = Duration::from_secs(min_interval_in_seconds);
+    }
+
+    /// Returns `true` if the JWK set was updated after `read_at`, either by `refresh` or by someone else.
+    ///
+    /// `last_updated` must return when the JWK set was last updated successfully.
+    pub(super) async fn run<L, LFut, R, RFut, E>(
+        &self,
+        read_at: Instant,
+        last_updated: L,
+        refresh: R,
+    ) -> Result<bool, E>
+    where
+        L: FnOnce() -> LFut,
+        LFut: Future<Output = Instant>,
+        R: FnOnce() -> RFut,
+        RFut: Future<Output = Result<(), E>>,
+    {
+        let mut last_forced_refresh = self.last_forced_refresh.lock().await;
+
+        // Concurrent requests with the new `kid` wait for the lock while the first one refreshes.
+        if last_updated().await > read_at {
+            return Ok(true);
+        }
+
+        if let Some(last_forced_refresh) = *last_forced_refresh {
+            if last_forced_refresh.elapsed() < self.min_interval {
+                tracing::debug!("Skipping forced JWK set refresh");
+
+                return Ok(false);
+            }
+        }
+
+        *last_forced_refresh = Some(Instant::now());
+
+        refresh().await?;
+
+        Ok(true)
+    }
+}
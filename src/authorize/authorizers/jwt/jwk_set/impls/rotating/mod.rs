@@ -1,7 +1,13 @@
 mod background_rotating_jwk_set_provider;
+mod cache_bounds;
+mod forced_refresh;
 mod rotating_jwk_set_provider;
+mod stale_policy;
 
 pub use background_rotating_jwk_set_provider::{
     BackgroundRotatingJwkSetProvideError, BackgroundRotatingJwkSetProvider,
+    BackgroundRotatingOptions,
 };
+pub use cache_bounds::CacheBounds;
 pub use rotating_jwk_set_provider::{RotatingJwkSetProvideError, RotatingJwkSetProvider};
+pub use stale_policy::{Backoff, StalePolicy};
@@ -1,23 +1,29 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use jsonwebtoken::jwk::JwkSet;
 use tokio::sync::{RwLock, RwLockReadGuard};
 
 use crate::authorize::{
-    authorizers::jwt::jwk_set::jwk_set_provider::JwkSetProvider, jwt::jwk_set::fetch::JwkSetFetcher,
+    authorizers::jwt::jwk_set::jwk_set_provider::JwkSetProvider,
+    jwt::jwk_set::fetch::{CacheMetadata, ConditionalJwkSet, JwkSetFetcher},
 };
 
+use super::{cache_bounds::CacheBounds, forced_refresh::ForcedRefresh};
+
 #[derive(Debug)]
 pub struct JwkSetHolder {
     last_updated: Instant,
+    cache: CacheMetadata,
     jwk_set: JwkSet,
 }
 
 #[derive(Debug)]
 pub struct RotatingJwkSetProvider<F> {
     time_to_live_in_seconds: u64,
+    cache_bounds: Option<CacheBounds>,
     jwk_set_fetcher: F,
     holder: RwLock<JwkSetHolder>,
+    forced_refresh: ForcedRefresh,
 }
 
 impl<F> RotatingJwkSetProvider<F>
@@ -28,48 +34,105 @@ where
         time_to_live_in_seconds: u64,
         jwk_set_fetcher: F,
     ) -> Result<Self, RotatingJwkSetProvideError<F::Error>> {
-        let jwk_set = jwk_set_fetcher
-            .fetch_jwk_set()
+        let (jwk_set, cache) = match jwk_set_fetcher
+            .fetch_jwk_set_conditional(&CacheMetadata::default())
             .await
-            .map_err(RotatingJwkSetProvideError::Fetch)?;
+            .map_err(RotatingJwkSetProvideError::Fetch)?
+        {
+            ConditionalJwkSet::Modified { jwk_set, cache } => (jwk_set, cache),
+            ConditionalJwkSet::NotModified { cache } => (
+                jwk_set_fetcher
+                    .fetch_jwk_set()
+                    .await
+                    .map_err(RotatingJwkSetProvideError::Fetch)?,
+                cache,
+            ),
+        };
 
         let last_updated = Instant::now();
 
         Ok(Self {
             time_to_live_in_seconds,
+            cache_bounds: None,
             jwk_set_fetcher,
             holder: RwLock::new(JwkSetHolder {
                 last_updated,
+                cache,
                 jwk_set,
             }),
+            forced_refresh: ForcedRefresh::new(30),
         })
     }
 
+    /// Minimum time between two refreshes triggered by tokens with an unknown `kid`. Defaults to 30 seconds.
+    pub fn min_forced_refresh_interval_in_seconds(
+        mut self,
+        min_forced_refresh_interval_in_seconds: u64,
+    ) -> Self {
+        self.forced_refresh
+            .set_min_interval_in_seconds(min_forced_refresh_interval_in_seconds);
+        self
+    }
+
+    /// Takes the time to live from the `Cache-Control: max-age` of the JWK set response, clamped to the given bounds.
+    ///
+    /// `time_to_live_in_seconds` is still used if the response has no `max-age`.
+    pub fn cache_bounds(mut self, cache_bounds: CacheBounds) -> Self {
+        self.cache_bounds = Some(cache_bounds);
+        self
+    }
+
     pub async fn refresh_jwk_set(
         &self,
     ) -> Result<impl AsRef<JwkSet> + use<'_, F>, RotatingJwkSetProvideError<F::Error>> {
         tracing::debug!("Refreshing JWK set");
 
-        let jwk_set = self
+        let cache = self.holder.read().await.cache.clone();
+
+        let fetched = self
             .jwk_set_fetcher
-            .fetch_jwk_set()
+            .fetch_jwk_set_conditional(&cache)
             .await
             .map_err(RotatingJwkSetProvideError::Fetch)?;
 
-        *(self.holder.write().await) = JwkSetHolder {
-            last_updated: Instant::now(),
-            jwk_set,
-        };
+        {
+            let mut holder = self.holder.write().await;
+
+            holder.last_updated = Instant::now();
+
+            match fetched {
+                ConditionalJwkSet::Modified { jwk_set, cache } => {
+                    holder.cache = cache;
+                    holder.jwk_set = jwk_set;
+                }
+                ConditionalJwkSet::NotModified { cache } => {
+                    holder.cache = cache;
+                }
+            }
+        }
 
         let guard = self.holder.read().await;
 
         Ok(JwkSetReadGuard::new(guard))
     }
 
+    fn time_to_live(&self, cache: &CacheMetadata) -> Duration {
+        let time_to_live = Duration::from_secs(self.time_to_live_in_seconds);
+
+        match &self.cache_bounds {
+            Some(cache_bounds) => cache_bounds.time_to_live(cache, time_to_live),
+            None => time_to_live,
+        }
+    }
+
     async fn get(&self) -> Result<&RwLock<JwkSetHolder>, RotatingJwkSetProvideError<F::Error>> {
-        let last_updated = self.holder.read().await.last_updated;
+        let expired = {
+            let holder = self.holder.read().await;
+
+            holder.last_updated.elapsed() > self.time_to_live(&holder.cache)
+        };
 
-        if last_updated.elapsed().as_secs() > self.time_to_live_in_seconds {
+        if expired {
             self.refresh_jwk_set().await?;
         }
 
@@ -93,6 +156,16 @@ where
 
         Ok(JwkSetReadGuard::new(guard))
     }
+
+    async fn refresh_jwk_set_on_unknown_kid(&self, read_at: Instant) -> Result<bool, Self::Error> {
+        self.forced_refresh
+            .run(
+                read_at,
+                || self.last_updated(),
+                || async { self.refresh_jwk_set().await.map(|_| ()) },
+            )
+            .await
+    }
 }
 
 impl AsRef<JwkSet> for JwkSetHolder {
@@ -124,7 +197,10 @@ pub enum RotatingJwkSetProvideError<F> {
 
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
 
     use jsonwebtoken::jwk::{
         AlgorithmParameters, CommonParameters, Jwk, JwkSet, OctetKeyParameters, OctetKeyType,
@@ -187,4 +263,105 @@ mod tests {
 
         assert_ne!(on_creation_jwks, current_jwks)
     }
+
+    #[tokio::test]
+    async fn forced_refresh_is_rate_limited() {
+        init_tracing();
+
+        let mut jwk_set_fetcher = MockJwkSetFetcher::default();
+
+        jwk_set_fetcher
+            .expect_fetch_jwk_set()
+            .times(2)
+            .returning(|| Box::pin(async { Ok(JwkSet { keys: Vec::new() }) }));
+
+        let rotating_jwk_set_provider = RotatingJwkSetProvider::new(60, jwk_set_fetcher)
+            .await
+            .expect("Failed to create rotating jwk set provider")
+            .min_forced_refresh_interval_in_seconds(60);
+
+        let refreshed = rotating_jwk_set_provider
+            .refresh_jwk_set_on_unknown_kid(Instant::now())
+            .await
+            .expect("Failed to refresh jwk set");
+
+        assert!(refreshed);
+
+        let refreshed = rotating_jwk_set_provider
+            .refresh_jwk_set_on_unknown_kid(Instant::now())
+            .await
+            .expect("Failed to refresh jwk set");
+
+        assert!(!refreshed);
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct CountingJwkSetFetcher {
+        fetches: Arc<AtomicUsize>,
+    }
+
+    impl JwkSetFetcher for CountingJwkSetFetcher {
+        type Error = anyhow::Error;
+
+        async fn fetch_jwk_set(&self) -> Result<JwkSet, Self::Error> {
+            unreachable!("Conditional fetch is overridden")
+        }
+
+        async fn fetch_jwk_set_conditional(
+            &self,
+            cache: &CacheMetadata,
+        ) -> Result<ConditionalJwkSet, Self::Error> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+
+            let fetched_cache = CacheMetadata {
+                max_age: Some(Duration::ZERO),
+                etag: Some(String::from("\"v1\"")),
+                last_modified: None,
+            };
+
+            if cache.etag == fetched_cache.etag {
+                return Ok(ConditionalJwkSet::NotModified {
+                    cache: fetched_cache,
+                });
+            }
+
+            Ok(ConditionalJwkSet::Modified {
+                jwk_set: JwkSet { keys: Vec::new() },
+                cache: fetched_cache,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn time_to_live_follows_cache_headers_within_bounds() {
+        init_tracing();
+
+        let jwk_set_fetcher = CountingJwkSetFetcher::default();
+        let fetches = jwk_set_fetcher.fetches.clone();
+
+        // The server sends `max-age=0`, which is raised to the lower bound of 1 second.
+        let rotating_jwk_set_provider = RotatingJwkSetProvider::new(60, jwk_set_fetcher)
+            .await
+            .expect("Failed to create rotating jwk set provider")
+            .cache_bounds(CacheBounds::new_in_seconds(1, 10));
+
+        let _ = rotating_jwk_set_provider
+            .provide_jwk_set()
+            .await
+            .expect("Failed to get jwk set");
+
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        let jwks = rotating_jwk_set_provider
+            .provide_jwk_set()
+            .await
+            .expect("Failed to get jwk set")
+            .as_ref()
+            .clone();
+
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+        assert!(jwks.keys.is_empty());
+    }
 }
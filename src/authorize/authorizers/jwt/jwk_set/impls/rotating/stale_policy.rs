@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+/// What [`BackgroundRotatingJwkSetProvider`](super::BackgroundRotatingJwkSetProvider) does when a refresh fails.
+#[derive(Debug, Clone, Default)]
+pub enum StalePolicy {
+    /// Return the refresh error to every caller until the next successful refresh.
+    #[default]
+    FailFast,
+    /// Keep serving the last good JWK set for up to `max_stale_in_seconds` after the last successful refresh,
+    /// retrying the refresh according to `backoff` in the meantime.
+    ServeStale {
+        max_stale_in_seconds: u64,
+        backoff: Backoff,
+    },
+}
+
+impl StalePolicy {
+    pub fn serve_stale(max_stale_in_seconds: u64) -> Self {
+        Self::ServeStale {
+            max_stale_in_seconds,
+            backoff: Backoff::default(),
+        }
+    }
+}
+
+/// Exponential backoff with jitter.
+///
+/// The delay doubles with every failed attempt, starting at `initial_delay` and capped at `max_delay`.
+/// A random fraction of up to `jitter` of the delay is then subtracted from it.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let backoff = Backoff::default()
+///     .initial_delay(Duration::from_secs(2))
+///     .max_delay(Duration::from_secs(120))
+///     .jitter(0.25);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    initial_delay: Duration,
+    max_delay: Duration,
+    jitter: f64,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            jitter: 0.5,
+        }
+    }
+}
+
+impl Backoff {
+    pub fn initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Clamped to `0.0..=1.0`. `NaN` disables the jitter.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = if jitter.is_nan() {
+            0.0
+        } else {
+            jitter.clamp(0.0, 1.0)
+        };
+        self
+    }
+
+    /// The delay before the given retry attempt, starting at `0`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let delay = self
+            .initial_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+
+        if self.jitter == 0.0 {
+            return delay;
+        }
+
+        delay.mul_f64(1.0 - rand::thread_rng().gen_range(0.0..=self.jitter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nan_jitter_is_disabled() {
+        let backoff = Backoff::default()
+            .initial_delay(Duration::from_secs(1))
+            .jitter(f64::NAN);
+
+        assert_eq!(backoff.delay(1), Duration::from_secs(2));
+    }
+}
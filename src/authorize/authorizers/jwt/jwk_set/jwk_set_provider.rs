@@ -1,4 +1,4 @@
-use std::future::Future;
+use std::{future::Future, time::Instant};
 
 use jsonwebtoken::jwk::JwkSet;
 
@@ -8,6 +8,18 @@ pub trait JwkSetProvider {
     fn provide_jwk_set(
         &self,
     ) -> impl Future<Output = Result<impl AsRef<JwkSet>, Self::Error>> + Send;
+
+    /// Called when a token references a key that is not in the JWK set provided at `read_at`.
+    ///
+    /// Returns `true` if the JWK set was updated since `read_at` and the token should be validated again.
+    /// Implementations should limit how often this actually refreshes,
+    /// since the `kid` is chosen by whoever sent the token.
+    fn refresh_jwk_set_on_unknown_kid(
+        &self,
+        _read_at: Instant,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send {
+        async { Ok(false) }
+    }
 }
 
 pub trait JwkSetProviderExt: Sized + JwkSetProvider {
@@ -48,4 +60,11 @@ where
             .await
             .map_err(|err| (self.map_err.clone())(err))
     }
+
+    async fn refresh_jwk_set_on_unknown_kid(&self, read_at: Instant) -> Result<bool, Self::Error> {
+        self.inner
+            .refresh_jwk_set_on_unknown_kid(read_at)
+            .await
+            .map_err(|err| (self.map_err.clone())(err))
+    }
 }
@@ -2,5 +2,8 @@ pub mod fetch;
 mod impls;
 mod jwk_set_provider;
 
-pub use impls::rotating;
+pub use impls::{
+    rotating,
+    static_jwk_set_provider::{StaticJwkSetError, StaticJwkSetProvider},
+};
 pub use jwk_set_provider::{JwkSetProvider, JwkSetProviderExt, MapError};
@@ -5,5 +5,9 @@ pub use impls::{
     default_jwt_authorizer::{
         DefaultJwtAuthorizeError, DefaultJwtAuthorizer, DefaultJwtAuthorizerBuilder,
     },
-    Validation,
+    multi_issuer_jwt_authorizer::{
+        MultiIssuerError, MultiIssuerJwtAuthorizeError, MultiIssuerJwtAuthorizer,
+        MultiIssuerJwtAuthorizerBuilder,
+    },
+    ClaimRule, ClaimRuleError, KeySelection, Validation,
 };
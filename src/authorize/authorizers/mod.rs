@@ -1,3 +1,9 @@
 pub mod api_key;
 pub mod basic_auth;
+pub mod digest;
+pub mod hmac;
+pub mod introspection;
 pub mod jwt;
+pub mod rbac;
+pub mod scope;
+pub mod session;
@@ -0,0 +1,3 @@
+pub mod permission_validator;
+pub mod role_registry;
+pub mod roles;
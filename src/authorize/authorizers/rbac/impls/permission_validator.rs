@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use crate::modify::Modifier;
+
+use super::{role_registry::RoleRegistry, roles::HasRoles};
+
+/// A [`Modifier`] that rejects extracted values whose roles do not grant a permission.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let registry = Arc::new(RoleRegistry::new().role("admin", ["orders:write"]));
+///
+/// let layer = PermissionValidator::new(registry, "orders:write").modification_layer::<Claims>();
+/// ```
+#[derive(Debug, Clone)]
+pub struct PermissionValidator {
+    registry: Arc<RoleRegistry>,
+    permission: Arc<str>,
+}
+
+impl PermissionValidator {
+    pub fn new(registry: Arc<RoleRegistry>, permission: impl Into<Arc<str>>) -> Self {
+        Self {
+            registry,
+            permission: permission.into(),
+        }
+    }
+
+    pub fn permission(&self) -> &str {
+        &self.permission
+    }
+}
+
+impl<T> Modifier<T> for PermissionValidator
+where
+    T: HasRoles + Clone + Send + Sync,
+{
+    type Modified = T;
+
+    type Error = MissingPermissionError;
+
+    async fn modify(&self, value: T) -> Result<Self::Modified, Self::Error> {
+        let granted = self
+            .registry
+            .has_permission(value.roles(), &self.permission);
+
+        if granted {
+            return Ok(value);
+        }
+
+        Err(MissingPermissionError {
+            permission: self.permission.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Missing permission: {permission}")]
+pub struct MissingPermissionError {
+    permission: String,
+}
+
+impl MissingPermissionError {
+    pub fn permission(&self) -> &str {
+        &self.permission
+    }
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use axum::response::{IntoResponse, Response};
+    use http::StatusCode;
+
+    use super::MissingPermissionError;
+
+    impl IntoResponse for MissingPermissionError {
+        fn into_response(self) -> Response {
+            tracing::warn!(err = %self, "Forbidden");
+
+            StatusCode::FORBIDDEN.into_response()
+        }
+    }
+
+    impl From<MissingPermissionError> for Response {
+        fn from(value: MissingPermissionError) -> Self {
+            value.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::authorize::rbac::KeycloakRoles;
+
+    use super::*;
+
+    #[derive(Debug, Clone, Deserialize)]
+    struct Claims {
+        #[serde(flatten)]
+        keycloak: KeycloakRoles,
+    }
+
+    impl HasRoles for Claims {
+        fn roles(&self) -> Vec<std::borrow::Cow<'_, str>> {
+            self.keycloak.roles()
+        }
+    }
+
+    #[tokio::test]
+    async fn permissions_are_inherited() {
+        let registry = Arc::new(
+            RoleRegistry::new()
+                .role("realm:user", ["orders:read"])
+                .role("realm:admin", ["orders:write"])
+                .role("client:billing:viewer", ["invoices:read"])
+                .inherits("realm:admin", ["realm:user"]),
+        );
+
+        let claims: Claims = serde_json::from_str(
+            r#"{
+                "realm_access": { "roles": ["admin"] },
+                "resource_access": { "billing": { "roles": ["viewer"] } }
+            }"#,
+        )
+        .expect("Valid claims");
+
+        PermissionValidator::new(registry.clone(), "invoices:read")
+            .modify(claims.clone())
+            .await
+            .expect("Client role grants invoices:read");
+
+        PermissionValidator::new(registry.clone(), "orders:read")
+            .modify(claims.clone())
+            .await
+            .expect("Inherited role grants orders:read");
+
+        let err = PermissionValidator::new(registry, "orders:delete")
+            .modify(claims)
+            .await
+            .expect_err("No role grants orders:delete");
+
+        assert_eq!(err.permission(), "orders:delete");
+    }
+}
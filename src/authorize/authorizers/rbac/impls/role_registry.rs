@@ -0,0 +1,91 @@
+use std::collections::{HashMap, HashSet};
+
+/// Maps roles to permissions.
+///
+/// Roles can inherit other roles, e.g. `admin` inheriting `user` grants `admin` every permission of `user`.
+///
+/// # Usage
+///
+/// ```rust
+/// use composable_tower_http::authorize::rbac::RoleRegistry;
+///
+/// let registry = RoleRegistry::new()
+///     .role("user", ["orders:read"])
+///     .role("admin", ["orders:write"])
+///     .inherits("admin", ["user"]);
+///
+/// assert!(registry.has_permission(["admin"], "orders:read"));
+/// assert!(!registry.has_permission(["user"], "orders:write"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RoleRegistry {
+    permissions: HashMap<String, HashSet<String>>,
+    parents: HashMap<String, HashSet<String>>,
+}
+
+impl RoleRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Grants `permissions` to `role`.
+    pub fn role<P: Into<String>>(
+        mut self,
+        role: impl Into<String>,
+        permissions: impl IntoIterator<Item = P>,
+    ) -> Self {
+        self.permissions
+            .entry(role.into())
+            .or_default()
+            .extend(permissions.into_iter().map(Into::into));
+        self
+    }
+
+    /// Grants `role` every permission of the `inherited` roles.
+    pub fn inherits<R: Into<String>>(
+        mut self,
+        role: impl Into<String>,
+        inherited: impl IntoIterator<Item = R>,
+    ) -> Self {
+        self.parents
+            .entry(role.into())
+            .or_default()
+            .extend(inherited.into_iter().map(Into::into));
+        self
+    }
+
+    /// All permissions of `role`, including inherited ones.
+    pub fn permissions(&self, role: &str) -> HashSet<&str> {
+        let mut permissions = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut pending = vec![role];
+
+        while let Some(role) = pending.pop() {
+            // Guards against cycles in the hierarchy.
+            if !visited.insert(role) {
+                continue;
+            }
+
+            if let Some(granted) = self.permissions.get(role) {
+                permissions.extend(granted.iter().map(String::as_str));
+            }
+
+            if let Some(parents) = self.parents.get(role) {
+                pending.extend(parents.iter().map(String::as_str));
+            }
+        }
+
+        permissions
+    }
+
+    /// Whether any of `roles` grants `permission`.
+    pub fn has_permission<R: AsRef<str>>(
+        &self,
+        roles: impl IntoIterator<Item = R>,
+        permission: &str,
+    ) -> bool {
+        roles
+            .into_iter()
+            .any(|role| self.permissions(role.as_ref()).contains(permission))
+    }
+}
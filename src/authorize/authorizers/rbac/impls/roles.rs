@@ -0,0 +1,110 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use serde::{Deserialize, Serialize};
+
+/// Implemented by extracted values that carry role names, usually the claims of a JWT.
+///
+/// The types in this module can be flattened into a claims type to read the roles of common identity providers.
+pub trait HasRoles {
+    fn roles(&self) -> Vec<Cow<'_, str>>;
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeycloakRoleList {
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+/// Keycloak's `realm_access.roles` and `resource_access.<client>.roles` claims.
+///
+/// Realm roles are returned as `realm:<role>`, client roles as `client:<client>:<role>`,
+/// so a client role can never grant the permissions of a realm role with the same name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeycloakRoles {
+    #[serde(default)]
+    pub realm_access: KeycloakRoleList,
+    #[serde(default)]
+    pub resource_access: HashMap<String, KeycloakRoleList>,
+}
+
+impl KeycloakRoles {
+    /// The roles of a single client from `resource_access`.
+    pub fn client_roles(&self, client: &str) -> Vec<&str> {
+        self.resource_access
+            .get(client)
+            .map(|access| access.roles.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl HasRoles for KeycloakRoles {
+    fn roles(&self) -> Vec<Cow<'_, str>> {
+        let realm_roles = self
+            .realm_access
+            .roles
+            .iter()
+            .map(|role| Cow::Owned(format!("realm:{role}")));
+
+        let client_roles = self.resource_access.iter().flat_map(|(client, access)| {
+            access
+                .roles
+                .iter()
+                .map(move |role| Cow::Owned(format!("client:{client}:{role}")))
+        });
+
+        realm_roles.chain(client_roles).collect()
+    }
+}
+
+/// Azure AD's `roles` claim.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AzureRoles {
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+impl HasRoles for AzureRoles {
+    fn roles(&self) -> Vec<Cow<'_, str>> {
+        self.roles
+            .iter()
+            .map(|role| Cow::Borrowed(role.as_str()))
+            .collect()
+    }
+}
+
+/// A plain `groups` claim, with each group used as a role.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Groups {
+    #[serde(default)]
+    pub groups: Vec<String>,
+}
+
+impl HasRoles for Groups {
+    fn roles(&self) -> Vec<Cow<'_, str>> {
+        self.groups
+            .iter()
+            .map(|group| Cow::Borrowed(group.as_str()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn realm_and_client_roles_do_not_collide() {
+        let roles: KeycloakRoles = serde_json::from_str(
+            r#"{
+                "realm_access": { "roles": ["billing:viewer"] },
+                "resource_access": { "billing": { "roles": ["viewer"] } }
+            }"#,
+        )
+        .expect("Valid claims");
+
+        let mut roles = roles.roles();
+        roles.sort();
+
+        assert_eq!(roles, ["client:billing:viewer", "realm:billing:viewer"]);
+    }
+}
@@ -0,0 +1,7 @@
+mod impls;
+
+pub use impls::{
+    permission_validator::{MissingPermissionError, PermissionValidator},
+    role_registry::RoleRegistry,
+    roles::{AzureRoles, Groups, HasRoles, KeycloakRoleList, KeycloakRoles},
+};
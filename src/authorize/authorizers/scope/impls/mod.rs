@@ -0,0 +1,2 @@
+pub mod scope_validator;
+pub mod scopes;
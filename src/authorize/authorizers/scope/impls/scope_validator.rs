@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use crate::modify::Modifier;
+
+use super::scopes::{HasScopes, ScopeRequirement};
+
+/// A [`Modifier`] that rejects extracted values lacking the required scopes.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let layer = ScopeValidator::all_of(["read:orders", "write:orders"]).modification_layer::<Claims>();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScopeValidator {
+    requirement: Arc<ScopeRequirement>,
+}
+
+impl ScopeValidator {
+    pub fn new(requirement: ScopeRequirement) -> Self {
+        Self {
+            requirement: Arc::new(requirement),
+        }
+    }
+
+    pub fn all_of<S: Into<String>>(scopes: impl IntoIterator<Item = S>) -> Self {
+        Self::new(ScopeRequirement::all_of(scopes))
+    }
+
+    pub fn any_of<S: Into<String>>(scopes: impl IntoIterator<Item = S>) -> Self {
+        Self::new(ScopeRequirement::any_of(scopes))
+    }
+
+    pub fn requirement(&self) -> &ScopeRequirement {
+        &self.requirement
+    }
+}
+
+impl<T> Modifier<T> for ScopeValidator
+where
+    T: HasScopes + Clone + Send + Sync,
+{
+    type Modified = T;
+
+    type Error = InsufficientScopeError;
+
+    async fn modify(&self, value: T) -> Result<Self::Modified, Self::Error> {
+        if self.requirement.is_satisfied_by(value.scopes()) {
+            return Ok(value);
+        }
+
+        Err(InsufficientScopeError {
+            required: self.requirement.as_ref().clone(),
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Insufficient scope. Required {required}")]
+pub struct InsufficientScopeError {
+    required: ScopeRequirement,
+}
+
+impl InsufficientScopeError {
+    pub fn required(&self) -> &ScopeRequirement {
+        &self.required
+    }
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use axum::response::{IntoResponse, Response};
+    use http::{header, HeaderValue, StatusCode};
+
+    use super::InsufficientScopeError;
+
+    impl IntoResponse for InsufficientScopeError {
+        fn into_response(self) -> Response {
+            tracing::warn!(err = %self, "Forbidden");
+
+            let challenge = format!(
+                r#"Bearer error="insufficient_scope", scope="{}""#,
+                self.required.scopes()
+            );
+
+            match HeaderValue::from_str(&challenge) {
+                Ok(challenge) => (
+                    StatusCode::FORBIDDEN,
+                    [(header::WWW_AUTHENTICATE, challenge)],
+                )
+                    .into_response(),
+                Err(_) => StatusCode::FORBIDDEN.into_response(),
+            }
+        }
+    }
+
+    impl From<InsufficientScopeError> for Response {
+        fn from(value: InsufficientScopeError) -> Self {
+            value.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::authorize::scope::Scopes;
+
+    use super::*;
+
+    #[derive(Debug, Clone, Deserialize)]
+    struct Claims {
+        #[serde(alias = "scp")]
+        scope: Scopes,
+    }
+
+    impl HasScopes for Claims {
+        fn scopes(&self) -> &Scopes {
+            &self.scope
+        }
+    }
+
+    #[tokio::test]
+    async fn scopes_are_required() {
+        let from_string: Claims =
+            serde_json::from_str(r#"{"scope":"openid read:orders"}"#).expect("Valid claims");
+        let from_array: Claims =
+            serde_json::from_str(r#"{"scp":["openid","read:orders"]}"#).expect("Valid claims");
+
+        assert_eq!(from_string.scope, from_array.scope);
+
+        let read = ScopeValidator::all_of(["read:orders"]);
+        let read_write = ScopeValidator::all_of(["read:orders", "write:orders"]);
+        let read_or_write = ScopeValidator::any_of(["read:orders", "write:orders"]);
+
+        read.modify(from_string.clone())
+            .await
+            .expect("read:orders is granted");
+
+        read_or_write
+            .modify(from_string.clone())
+            .await
+            .expect("read:orders is granted");
+
+        let err = read_write
+            .modify(from_string)
+            .await
+            .expect_err("write:orders is not granted");
+
+        assert_eq!(
+            err.required().scopes().to_string(),
+            "read:orders write:orders"
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use std::{collections::BTreeSet, fmt};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// OAuth2 scopes.
+///
+/// Deserializes from a space-separated string (the `scope` claim) or an array of strings (the `scp` claim),
+/// and serializes to a space-separated string.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Scopes(BTreeSet<String>);
+
+impl Scopes {
+    pub fn new<S: Into<String>>(scopes: impl IntoIterator<Item = S>) -> Self {
+        Self(scopes.into_iter().map(Into::into).collect())
+    }
+
+    pub fn insert(&mut self, scope: impl Into<String>) {
+        self.0.insert(scope.into());
+    }
+
+    pub fn contains(&self, scope: &str) -> bool {
+        self.0.contains(scope)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<&str> for Scopes {
+    fn from(value: &str) -> Self {
+        Self::new(value.split_whitespace())
+    }
+}
+
+impl fmt::Display for Scopes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.0.iter().cloned().collect::<Vec<_>>().join(" ")
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for Scopes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawScopes {
+            String(String),
+            Array(Vec<String>),
+        }
+
+        match RawScopes::deserialize(deserializer)? {
+            RawScopes::String(scopes) => Ok(Self::from(scopes.as_str())),
+            RawScopes::Array(scopes) => Ok(Self::new(scopes)),
+        }
+    }
+}
+
+impl Serialize for Scopes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Implemented by extracted values that carry OAuth2 scopes, usually the claims of an access token.
+pub trait HasScopes {
+    fn scopes(&self) -> &Scopes;
+}
+
+impl HasScopes for Scopes {
+    fn scopes(&self) -> &Scopes {
+        self
+    }
+}
+
+/// The scopes a route requires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScopeRequirement {
+    /// Every scope is required.
+    AllOf(Scopes),
+    /// At least one of the scopes is required.
+    AnyOf(Scopes),
+}
+
+impl ScopeRequirement {
+    pub fn all_of<S: Into<String>>(scopes: impl IntoIterator<Item = S>) -> Self {
+        Self::AllOf(Scopes::new(scopes))
+    }
+
+    pub fn any_of<S: Into<String>>(scopes: impl IntoIterator<Item = S>) -> Self {
+        Self::AnyOf(Scopes::new(scopes))
+    }
+
+    pub fn scopes(&self) -> &Scopes {
+        match self {
+            ScopeRequirement::AllOf(scopes) => scopes,
+            ScopeRequirement::AnyOf(scopes) => scopes,
+        }
+    }
+
+    pub fn is_satisfied_by(&self, scopes: &Scopes) -> bool {
+        match self {
+            ScopeRequirement::AllOf(required) => required.iter().all(|s| scopes.contains(s)),
+            ScopeRequirement::AnyOf(required) => required.iter().any(|s| scopes.contains(s)),
+        }
+    }
+}
+
+impl fmt::Display for ScopeRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScopeRequirement::AllOf(scopes) => write!(f, "all of [{scopes}]"),
+            ScopeRequirement::AnyOf(scopes) => write!(f, "any of [{scopes}]"),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+mod impls;
+
+pub use impls::{
+    scope_validator::{InsufficientScopeError, ScopeValidator},
+    scopes::{HasScopes, ScopeRequirement, Scopes},
+};
@@ -0,0 +1,5 @@
+pub mod session;
+pub mod session_authorizer;
+pub mod session_cookie;
+pub mod session_key;
+pub mod stateless_session_authorizer;
@@ -0,0 +1,51 @@
+use std::time::{Duration, SystemTime};
+
+/// A server-side session, resolved from a session id by a [`SessionStore`](super::super::store::SessionStore).
+#[derive(Debug, Clone)]
+pub struct Session<P> {
+    pub principal: P,
+    pub created_at: SystemTime,
+    pub last_seen_at: SystemTime,
+}
+
+impl<P> Session<P> {
+    /// Creates a session that starts now.
+    pub fn new(principal: P) -> Self {
+        let now = SystemTime::now();
+
+        Self {
+            principal,
+            created_at: now,
+            last_seen_at: now,
+        }
+    }
+
+    /// A timeout too large to represent never elapses.
+    pub(crate) fn is_idle(&self, idle_timeout: Duration, now: SystemTime) -> bool {
+        self.last_seen_at
+            .checked_add(idle_timeout)
+            .is_some_and(|idle_at| idle_at <= now)
+    }
+
+    /// A timeout too large to represent never elapses.
+    pub(crate) fn is_expired(&self, absolute_timeout: Duration, now: SystemTime) -> bool {
+        self.created_at
+            .checked_add(absolute_timeout)
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflowing_timeouts_never_elapse() {
+        let session = Session::new(());
+        let now = SystemTime::now();
+
+        assert!(!session.is_idle(Duration::MAX, now));
+        assert!(!session.is_expired(Duration::MAX, now));
+        assert!(session.is_expired(Duration::ZERO, now));
+    }
+}
@@ -0,0 +1,271 @@
+use std::{
+    ops::Deref,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use http::request::Parts;
+
+use crate::{
+    authorize::{header::HeaderExtractor, session::store::SessionStore},
+    extract::Extractor,
+};
+
+#[derive(Debug)]
+pub struct SessionAuthorizerInner<H, S> {
+    header_extractor: H,
+    session_store: S,
+    idle_timeout: Option<Duration>,
+    absolute_timeout: Option<Duration>,
+}
+
+impl<H, S> SessionAuthorizerInner<H, S> {
+    pub fn session_store(&self) -> &S {
+        &self.session_store
+    }
+}
+
+/// Resolves a session id, e.g. from a [`CookieExtractor`](crate::authorize::header::cookie::CookieExtractor),
+/// to the principal of a server-side session.
+///
+/// Sessions that were idle for longer than the idle timeout
+/// or were created longer than the absolute timeout ago are removed and rejected.
+#[derive(Debug)]
+pub struct SessionAuthorizer<H, S> {
+    inner: Arc<SessionAuthorizerInner<H, S>>,
+}
+
+impl<H, S> Clone for SessionAuthorizer<H, S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<H, S> Deref for SessionAuthorizer<H, S> {
+    type Target = SessionAuthorizerInner<H, S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<H, S> Extractor for SessionAuthorizer<H, S>
+where
+    H: HeaderExtractor + Send + Sync,
+    S: SessionStore + Send + Sync,
+    S::Principal: Clone + Send + Sync + 'static,
+{
+    type Extracted = S::Principal;
+
+    type Error = SessionAuthorizeError<H::Error, S::Error>;
+
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+        let id = self
+            .header_extractor
+            .extract_header(&parts.headers)
+            .map_err(SessionAuthorizeError::Header)?;
+
+        let session = self
+            .session_store
+            .load(id)
+            .await
+            .map_err(SessionAuthorizeError::Store)?
+            .ok_or(SessionAuthorizeError::Unknown)?;
+
+        let now = SystemTime::now();
+
+        let expired = self
+            .absolute_timeout
+            .is_some_and(|timeout| session.is_expired(timeout, now));
+
+        let idle = self
+            .idle_timeout
+            .is_some_and(|timeout| session.is_idle(timeout, now));
+
+        if expired || idle {
+            self.session_store
+                .remove(id)
+                .await
+                .map_err(SessionAuthorizeError::Store)?;
+
+            if expired {
+                return Err(SessionAuthorizeError::Expired);
+            }
+
+            return Err(SessionAuthorizeError::Idle);
+        }
+
+        self.session_store
+            .touch(id, now)
+            .await
+            .map_err(SessionAuthorizeError::Store)?;
+
+        Ok(session.principal)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SessionAuthorizeError<H, S> {
+    #[error("Header extraction error: {0}")]
+    Header(#[source] H),
+    #[error("Session store error: {0}")]
+    Store(#[source] S),
+    #[error("Unknown session")]
+    Unknown,
+    #[error("Session idle timeout exceeded")]
+    Idle,
+    #[error("Session absolute timeout exceeded")]
+    Expired,
+}
+
+/// Builds a [`SessionAuthorizer`].
+///
+/// Sessions do not expire unless timeouts are configured.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let session_authorizer = SessionAuthorizerBuilder::new(CookieExtractor::new("session"), session_store)
+///     .idle_timeout_in_seconds(30 * 60)
+///     .absolute_timeout_in_seconds(8 * 60 * 60)
+///     .build();
+/// ```
+#[derive(Debug)]
+pub struct SessionAuthorizerBuilder<H, S> {
+    header_extractor: H,
+    session_store: S,
+    idle_timeout: Option<Duration>,
+    absolute_timeout: Option<Duration>,
+}
+
+impl<H, S> SessionAuthorizerBuilder<H, S> {
+    pub fn new(header_extractor: H, session_store: S) -> Self {
+        Self {
+            header_extractor,
+            session_store,
+            idle_timeout: None,
+            absolute_timeout: None,
+        }
+    }
+
+    /// Rejects sessions that were not used for `idle_timeout_in_seconds`.
+    pub fn idle_timeout_in_seconds(mut self, idle_timeout_in_seconds: u64) -> Self {
+        self.idle_timeout = Some(Duration::from_secs(idle_timeout_in_seconds));
+        self
+    }
+
+    /// Rejects sessions that were created more than `absolute_timeout_in_seconds` ago, even if they are in use.
+    pub fn absolute_timeout_in_seconds(mut self, absolute_timeout_in_seconds: u64) -> Self {
+        self.absolute_timeout = Some(Duration::from_secs(absolute_timeout_in_seconds));
+        self
+    }
+
+    pub fn build(self) -> SessionAuthorizer<H, S> {
+        SessionAuthorizer {
+            inner: Arc::new(SessionAuthorizerInner {
+                header_extractor: self.header_extractor,
+                session_store: self.session_store,
+                idle_timeout: self.idle_timeout,
+                absolute_timeout: self.absolute_timeout,
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use axum::response::{IntoResponse, Response};
+    use http::StatusCode;
+
+    use super::SessionAuthorizeError;
+
+    impl<H, S> IntoResponse for SessionAuthorizeError<H, S>
+    where
+        H: std::error::Error,
+        S: std::error::Error,
+    {
+        fn into_response(self) -> Response {
+            tracing::warn!(err = %self, "Unauthorized");
+
+            match self {
+                SessionAuthorizeError::Store(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                _ => StatusCode::UNAUTHORIZED,
+            }
+            .into_response()
+        }
+    }
+
+    impl<H, S> From<SessionAuthorizeError<H, S>> for Response
+    where
+        H: std::error::Error,
+        S: std::error::Error,
+    {
+        fn from(value: SessionAuthorizeError<H, S>) -> Self {
+            value.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Request;
+
+    use crate::authorize::{
+        header::cookie::CookieExtractor,
+        session::{store::InMemorySessionStore, Session},
+    };
+
+    use super::*;
+
+    fn parts(id: &str) -> Parts {
+        Request::builder()
+            .header("cookie", format!("session={id}"))
+            .body(())
+            .expect("Valid request")
+            .into_parts()
+            .0
+    }
+
+    #[tokio::test]
+    async fn sessions_expire() {
+        let store = InMemorySessionStore::new();
+
+        let authorizer =
+            SessionAuthorizerBuilder::new(CookieExtractor::new("session"), store.clone())
+                .idle_timeout_in_seconds(60)
+                .absolute_timeout_in_seconds(3600)
+                .build();
+
+        let id = store.create(String::from("user"));
+
+        let principal = authorizer
+            .extract(&parts(&id))
+            .await
+            .expect("Session should be valid");
+
+        assert_eq!(principal, "user");
+
+        let now = SystemTime::now();
+
+        let mut idle = Session::new(String::from("idle"));
+        idle.last_seen_at = now - Duration::from_secs(120);
+        store.insert("idle", idle);
+
+        let mut expired = Session::new(String::from("expired"));
+        expired.created_at = now - Duration::from_secs(7200);
+        store.insert("expired", expired);
+
+        let result = authorizer.extract(&parts("idle")).await;
+        assert!(matches!(result, Err(SessionAuthorizeError::Idle)));
+
+        let result = authorizer.extract(&parts("expired")).await;
+        assert!(matches!(result, Err(SessionAuthorizeError::Expired)));
+
+        let result = authorizer.extract(&parts("idle")).await;
+        assert!(matches!(result, Err(SessionAuthorizeError::Unknown)));
+
+        assert_eq!(store.len(), 1);
+    }
+}
@@ -0,0 +1,216 @@
+use std::{borrow::Cow, time::Duration};
+
+use http::HeaderValue;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+impl SameSite {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        }
+    }
+}
+
+/// The name and `Set-Cookie` attributes of a session cookie.
+///
+/// Defaults to `Path=/; Secure; HttpOnly; SameSite=Lax`.
+///
+/// The name, path and domain are validated when they are set, so building a `Set-Cookie` header only fails for invalid values.
+#[derive(Debug, Clone)]
+pub struct SessionCookie {
+    name: Cow<'static, str>,
+    path: Cow<'static, str>,
+    domain: Option<Cow<'static, str>>,
+    secure: bool,
+    http_only: bool,
+    same_site: Option<SameSite>,
+}
+
+impl SessionCookie {
+    /// `name` must be a non-empty token as defined by RFC 6265.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Result<Self, SessionCookieError> {
+        let name = name.into();
+
+        if name.is_empty() || !name.bytes().all(is_token) {
+            return Err(SessionCookieError::Name(name.into_owned()));
+        }
+
+        Ok(Self {
+            name,
+            path: Cow::Borrowed("/"),
+            domain: None,
+            secure: true,
+            http_only: true,
+            same_site: Some(SameSite::Lax),
+        })
+    }
+
+    pub fn path(mut self, path: impl Into<Cow<'static, str>>) -> Result<Self, SessionCookieError> {
+        let path = path.into();
+
+        if !is_attribute_value(&path) {
+            return Err(SessionCookieError::Path(path.into_owned()));
+        }
+
+        self.path = path;
+
+        Ok(self)
+    }
+
+    pub fn domain(
+        mut self,
+        domain: impl Into<Cow<'static, str>>,
+    ) -> Result<Self, SessionCookieError> {
+        let domain = domain.into();
+
+        if !is_attribute_value(&domain) {
+            return Err(SessionCookieError::Domain(domain.into_owned()));
+        }
+
+        self.domain = Some(domain);
+
+        Ok(self)
+    }
+
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    pub fn http_only(mut self, http_only: bool) -> Self {
+        self.http_only = http_only;
+        self
+    }
+
+    /// `None` omits the `SameSite` attribute.
+    pub fn same_site(mut self, same_site: Option<SameSite>) -> Self {
+        self.same_site = same_site;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Builds a `Set-Cookie` header value.
+    ///
+    /// `value` must only contain the cookie value characters of RFC 6265.
+    pub fn set_cookie(
+        &self,
+        value: &str,
+        max_age: Duration,
+    ) -> Result<HeaderValue, SessionCookieError> {
+        if !value.bytes().all(is_cookie_octet) {
+            return Err(SessionCookieError::Value);
+        }
+
+        HeaderValue::try_from(self.format(value, max_age)).map_err(|_| SessionCookieError::Value)
+    }
+
+    /// Builds a `Set-Cookie` header value that removes the cookie.
+    pub fn remove_cookie(&self) -> HeaderValue {
+        HeaderValue::try_from(self.format("", Duration::ZERO))
+            .expect("Name, path and domain are validated")
+    }
+
+    fn format(&self, value: &str, max_age: Duration) -> String {
+        let mut cookie = format!(
+            "{}={}; Path={}; Max-Age={}",
+            self.name,
+            value,
+            self.path,
+            max_age.as_secs()
+        );
+
+        if let Some(domain) = &self.domain {
+            cookie.push_str("; Domain=");
+            cookie.push_str(domain);
+        }
+
+        if self.secure {
+            cookie.push_str("; Secure");
+        }
+
+        if self.http_only {
+            cookie.push_str("; HttpOnly");
+        }
+
+        if let Some(same_site) = self.same_site {
+            cookie.push_str("; SameSite=");
+            cookie.push_str(same_site.as_str());
+        }
+
+        cookie
+    }
+}
+
+fn is_token(byte: u8) -> bool {
+    byte.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?={}".contains(&byte)
+}
+
+fn is_cookie_octet(byte: u8) -> bool {
+    byte.is_ascii_graphic() && !b"\",;\\".contains(&byte)
+}
+
+fn is_attribute_value(value: &str) -> bool {
+    value
+        .bytes()
+        .all(|byte| byte.is_ascii_graphic() && byte != b';')
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SessionCookieError {
+    #[error("Invalid cookie name: {0}")]
+    Name(String),
+    #[error("Invalid cookie path: {0}")]
+    Path(String),
+    #[error("Invalid cookie domain: {0}")]
+    Domain(String),
+    #[error("Invalid cookie value")]
+    Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_cookies_are_rejected() {
+        assert!(matches!(
+            SessionCookie::new("session id"),
+            Err(SessionCookieError::Name(_))
+        ));
+
+        let cookie = SessionCookie::new("session").expect("Valid cookie name");
+
+        assert!(matches!(
+            cookie.clone().path("/; HttpOnly"),
+            Err(SessionCookieError::Path(_))
+        ));
+
+        assert!(matches!(
+            cookie.clone().domain("example.com\r\n"),
+            Err(SessionCookieError::Domain(_))
+        ));
+
+        assert!(matches!(
+            cookie.set_cookie("a;b", Duration::ZERO),
+            Err(SessionCookieError::Value)
+        ));
+
+        assert_eq!(
+            cookie
+                .set_cookie("abc", Duration::from_secs(60))
+                .expect("Valid cookie value"),
+            "session=abc; Path=/; Max-Age=60; Secure; HttpOnly; SameSite=Lax"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use std::borrow::Cow;
+
+/// A key used to sign or encrypt stateless session cookies.
+///
+/// The id is written into every cookie, so that cookies issued with older keys can still be verified.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SessionKey {
+    pub id: Cow<'static, str>,
+    pub value: Cow<'static, [u8]>,
+}
+
+impl core::fmt::Debug for SessionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionKey")
+            .field("id", &self.id)
+            .field("value", &"...")
+            .finish()
+    }
+}
+
+impl SessionKey {
+    pub fn new(id: impl Into<Cow<'static, str>>, value: impl Into<Cow<'static, [u8]>>) -> Self {
+        Self {
+            id: id.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// The keys of stateless sessions.
+///
+/// New cookies are issued with the current key.
+/// Previous keys are only used to verify cookies, until they are removed from the ring.
+#[derive(Debug, Clone)]
+pub struct SessionKeyRing {
+    current: SessionKey,
+    previous: Vec<SessionKey>,
+}
+
+impl SessionKeyRing {
+    pub fn new(current: SessionKey) -> Self {
+        Self {
+            current,
+            previous: Vec::new(),
+        }
+    }
+
+    /// Adds a key that is still accepted, but no longer used to issue cookies.
+    pub fn previous(mut self, key: SessionKey) -> Self {
+        self.previous.push(key);
+        self
+    }
+
+    pub fn current(&self) -> &SessionKey {
+        &self.current
+    }
+
+    pub fn find(&self, id: &str) -> Option<&SessionKey> {
+        std::iter::once(&self.current)
+            .chain(self.previous.iter())
+            .find(|key| key.id == id)
+    }
+}
@@ -0,0 +1,483 @@
+use std::{
+    marker::PhantomData,
+    ops::Deref,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aes_gcm::{
+    aead::{Aead, Payload},
+    Aes256Gcm, KeyInit, Nonce,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use http::{request::Parts, HeaderValue};
+use rand::{rngs::OsRng, RngCore};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::{
+    authorize::header::{
+        cookie::{CookieError, CookieExtractor},
+        HeaderExtractor,
+    },
+    extract::Extractor,
+};
+
+use super::{
+    session_cookie::{SessionCookie, SessionCookieError},
+    session_key::{SessionKey, SessionKeyRing},
+};
+
+const NONCE_LEN: usize = 12;
+
+/// How the contents of a stateless session cookie are protected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionProtection {
+    /// HMAC-SHA256. The principal is readable by the client, but can not be modified.
+    Signed,
+    /// AES-256-GCM. The principal is neither readable nor modifiable by the client.
+    ///
+    /// Keys must be 32 bytes long.
+    Encrypted,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StatelessSession<P> {
+    #[serde(rename = "p")]
+    principal: P,
+    exp: u64,
+}
+
+#[derive(Debug)]
+pub struct StatelessSessionAuthorizerInner<P> {
+    key_ring: SessionKeyRing,
+    protection: SessionProtection,
+    cookie: SessionCookie,
+    cookie_extractor: CookieExtractor,
+    max_age: Duration,
+    _principal: PhantomData<P>,
+}
+
+impl<P> StatelessSessionAuthorizerInner<P> {
+    pub fn cookie(&self) -> &SessionCookie {
+        &self.cookie
+    }
+
+    /// Builds the `Set-Cookie` header value of a new session for `principal`, e.g. for a login response.
+    pub fn issue(&self, principal: &P) -> Result<HeaderValue, StatelessSessionIssueError>
+    where
+        P: Serialize,
+    {
+        let exp = unix_now().saturating_add(self.max_age.as_secs());
+
+        let session = StatelessSession { principal, exp };
+
+        let payload =
+            serde_json::to_vec(&session).map_err(StatelessSessionIssueError::Serialize)?;
+
+        let key = self.key_ring.current();
+        let kid = URL_SAFE_NO_PAD.encode(key.id.as_bytes());
+
+        let value = match self.protection {
+            SessionProtection::Signed => {
+                let payload = URL_SAFE_NO_PAD.encode(payload);
+                let tag = sign(key, &kid, &payload);
+
+                format!("{kid}.{payload}.{}", URL_SAFE_NO_PAD.encode(tag))
+            }
+            SessionProtection::Encrypted => {
+                let cipher = Aes256Gcm::new_from_slice(&key.value).map_err(|_| {
+                    StatelessSessionIssueError::KeyLength {
+                        kid: key.id.to_string(),
+                    }
+                })?;
+
+                let mut nonce = [0u8; NONCE_LEN];
+                OsRng.fill_bytes(&mut nonce);
+
+                let ciphertext = cipher
+                    .encrypt(
+                        Nonce::from_slice(&nonce),
+                        Payload {
+                            msg: &payload,
+                            aad: kid.as_bytes(),
+                        },
+                    )
+                    .map_err(|_| StatelessSessionIssueError::Encrypt)?;
+
+                format!(
+                    "{kid}.{}.{}",
+                    URL_SAFE_NO_PAD.encode(nonce),
+                    URL_SAFE_NO_PAD.encode(ciphertext)
+                )
+            }
+        };
+
+        self.cookie
+            .set_cookie(&value, self.max_age)
+            .map_err(StatelessSessionIssueError::Cookie)
+    }
+
+    /// Builds the `Set-Cookie` header value that ends the session on the client, e.g. for a logout response.
+    ///
+    /// Stateless sessions can not be revoked. A copy of the cookie stays valid until it expires.
+    pub fn clear(&self) -> HeaderValue {
+        self.cookie.remove_cookie()
+    }
+
+    pub fn open(&self, value: &str) -> Result<P, StatelessSessionError>
+    where
+        P: DeserializeOwned,
+    {
+        let mut split = value.splitn(3, '.');
+
+        let (Some(kid), Some(first), Some(second)) = (split.next(), split.next(), split.next())
+        else {
+            return Err(StatelessSessionError::Malformed);
+        };
+
+        let id = URL_SAFE_NO_PAD
+            .decode(kid)
+            .ok()
+            .and_then(|id| String::from_utf8(id).ok())
+            .ok_or(StatelessSessionError::Malformed)?;
+
+        let key = self
+            .key_ring
+            .find(&id)
+            .ok_or(StatelessSessionError::UnknownKey { kid: id.clone() })?;
+
+        let payload = match self.protection {
+            SessionProtection::Signed => {
+                let tag = URL_SAFE_NO_PAD
+                    .decode(second)
+                    .map_err(|_| StatelessSessionError::Malformed)?;
+
+                verify(key, kid, first, &tag)?;
+
+                URL_SAFE_NO_PAD
+                    .decode(first)
+                    .map_err(|_| StatelessSessionError::Malformed)?
+            }
+            SessionProtection::Encrypted => {
+                let nonce = URL_SAFE_NO_PAD
+                    .decode(first)
+                    .ok()
+                    .filter(|nonce| nonce.len() == NONCE_LEN)
+                    .ok_or(StatelessSessionError::Malformed)?;
+
+                let ciphertext = URL_SAFE_NO_PAD
+                    .decode(second)
+                    .map_err(|_| StatelessSessionError::Malformed)?;
+
+                let cipher = Aes256Gcm::new_from_slice(&key.value)
+                    .map_err(|_| StatelessSessionError::KeyLength { kid: id })?;
+
+                cipher
+                    .decrypt(
+                        Nonce::from_slice(&nonce),
+                        Payload {
+                            msg: &ciphertext,
+                            aad: kid.as_bytes(),
+                        },
+                    )
+                    .map_err(|_| StatelessSessionError::Decrypt)?
+            }
+        };
+
+        let session = serde_json::from_slice::<StatelessSession<P>>(&payload)
+            .map_err(StatelessSessionError::Deserialize)?;
+
+        if session.exp <= unix_now() {
+            return Err(StatelessSessionError::Expired);
+        }
+
+        Ok(session.principal)
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn mac(key: &SessionKey, kid: &str, payload: &str) -> Hmac<Sha256> {
+    let mut mac =
+        <Hmac<Sha256> as Mac>::new_from_slice(&key.value).expect("HMAC can take a key of any size");
+
+    mac.update(kid.as_bytes());
+    mac.update(b".");
+    mac.update(payload.as_bytes());
+
+    mac
+}
+
+fn sign(key: &SessionKey, kid: &str, payload: &str) -> Vec<u8> {
+    mac(key, kid, payload).finalize().into_bytes().to_vec()
+}
+
+fn verify(
+    key: &SessionKey,
+    kid: &str,
+    payload: &str,
+    tag: &[u8],
+) -> Result<(), StatelessSessionError> {
+    mac(key, kid, payload)
+        .verify_slice(tag)
+        .map_err(|_| StatelessSessionError::InvalidSignature)
+}
+
+/// Reads a principal from a signed or encrypted session cookie, without any server-side state.
+///
+/// The same authorizer issues the cookies with [`StatelessSessionAuthorizerInner::issue`].
+#[derive(Debug)]
+pub struct StatelessSessionAuthorizer<P> {
+    inner: Arc<StatelessSessionAuthorizerInner<P>>,
+}
+
+impl<P> Clone for StatelessSessionAuthorizer<P> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<P> Deref for StatelessSessionAuthorizer<P> {
+    type Target = StatelessSessionAuthorizerInner<P>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<P> Extractor for StatelessSessionAuthorizer<P>
+where
+    P: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    type Extracted = P;
+
+    type Error = StatelessSessionError;
+
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+        let value = self.cookie_extractor.extract_header(&parts.headers)?;
+
+        self.open(value)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StatelessSessionError {
+    #[error("Cookie extraction error: {0}")]
+    Cookie(
+        #[source]
+        #[from]
+        CookieError,
+    ),
+    #[error("Malformed session cookie")]
+    Malformed,
+    #[error("Unknown session key: {kid}")]
+    UnknownKey { kid: String },
+    #[error("Session key {kid} is not 32 bytes long")]
+    KeyLength { kid: String },
+    #[error("Invalid session signature")]
+    InvalidSignature,
+    #[error("Session decryption failed")]
+    Decrypt,
+    #[error("Session deserialization error: {0}")]
+    Deserialize(#[source] serde_json::Error),
+    #[error("Session expired")]
+    Expired,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StatelessSessionIssueError {
+    #[error("Session serialization error: {0}")]
+    Serialize(#[source] serde_json::Error),
+    #[error("Session key {kid} is not 32 bytes long")]
+    KeyLength { kid: String },
+    #[error("Session encryption failed")]
+    Encrypt,
+    #[error("Session cookie error: {0}")]
+    Cookie(#[source] SessionCookieError),
+}
+
+/// Builds a [`StatelessSessionAuthorizer`].
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let key_ring = SessionKeyRing::new(SessionKey::new("2024-06", current_key))
+///     .previous(SessionKey::new("2024-05", previous_key));
+///
+/// let session_authorizer = StatelessSessionAuthorizerBuilder::encrypted(key_ring)
+///     .cookie(SessionCookie::new("session")?.same_site(Some(SameSite::Strict)))
+///     .max_age_in_seconds(8 * 60 * 60)
+///     .build::<User>();
+/// ```
+#[derive(Debug)]
+pub struct StatelessSessionAuthorizerBuilder {
+    key_ring: SessionKeyRing,
+    protection: SessionProtection,
+    cookie: SessionCookie,
+    max_age: Duration,
+}
+
+impl StatelessSessionAuthorizerBuilder {
+    /// Defaults to a cookie named `session` that is valid for one day.
+    pub fn new(key_ring: SessionKeyRing, protection: SessionProtection) -> Self {
+        Self {
+            key_ring,
+            protection,
+            cookie: SessionCookie::new("session").expect("`session` is a valid cookie name"),
+            max_age: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+
+    pub fn signed(key_ring: SessionKeyRing) -> Self {
+        Self::new(key_ring, SessionProtection::Signed)
+    }
+
+    pub fn encrypted(key_ring: SessionKeyRing) -> Self {
+        Self::new(key_ring, SessionProtection::Encrypted)
+    }
+
+    pub fn cookie(mut self, cookie: SessionCookie) -> Self {
+        self.cookie = cookie;
+        self
+    }
+
+    /// How long issued sessions are valid. Used for the cookie's `Max-Age` and checked on every request.
+    pub fn max_age_in_seconds(mut self, max_age_in_seconds: u64) -> Self {
+        self.max_age = Duration::from_secs(max_age_in_seconds);
+        self
+    }
+
+    pub fn build<P>(self) -> StatelessSessionAuthorizer<P> {
+        let cookie_extractor = CookieExtractor::new(self.cookie.name().to_string());
+
+        StatelessSessionAuthorizer {
+            inner: Arc::new(StatelessSessionAuthorizerInner {
+                key_ring: self.key_ring,
+                protection: self.protection,
+                cookie: self.cookie,
+                cookie_extractor,
+                max_age: self.max_age,
+                _principal: PhantomData,
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use axum::response::{IntoResponse, Response};
+    use http::StatusCode;
+
+    use super::{StatelessSessionError, StatelessSessionIssueError};
+
+    impl IntoResponse for StatelessSessionError {
+        fn into_response(self) -> Response {
+            tracing::warn!(err = %self, "Unauthorized");
+
+            match self {
+                StatelessSessionError::KeyLength { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+                _ => StatusCode::UNAUTHORIZED,
+            }
+            .into_response()
+        }
+    }
+
+    impl From<StatelessSessionError> for Response {
+        fn from(value: StatelessSessionError) -> Self {
+            value.into_response()
+        }
+    }
+
+    impl IntoResponse for StatelessSessionIssueError {
+        fn into_response(self) -> Response {
+            tracing::error!(err = %self, "Failed to issue session");
+
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+
+    impl From<StatelessSessionIssueError> for Response {
+        fn from(value: StatelessSessionIssueError) -> Self {
+            value.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        name: String,
+    }
+
+    fn cookie_value(set_cookie: &HeaderValue) -> &str {
+        let set_cookie = set_cookie.to_str().expect("Valid header");
+
+        let (pair, _) = set_cookie.split_once(';').expect("Cookie has attributes");
+        let (_, value) = pair.split_once('=').expect("Cookie has a value");
+
+        value
+    }
+
+    #[test]
+    fn sessions_survive_key_rotation() {
+        let user = User {
+            name: String::from("user"),
+        };
+
+        for protection in [SessionProtection::Signed, SessionProtection::Encrypted] {
+            let old = StatelessSessionAuthorizerBuilder::new(
+                SessionKeyRing::new(SessionKey::new("1", [1u8; 32].to_vec())),
+                protection,
+            )
+            .build::<User>();
+
+            let rotated = StatelessSessionAuthorizerBuilder::new(
+                SessionKeyRing::new(SessionKey::new("2", [2u8; 32].to_vec()))
+                    .previous(SessionKey::new("1", [1u8; 32].to_vec())),
+                protection,
+            )
+            .build::<User>();
+
+            let set_cookie = old.issue(&user).expect("Failed to issue session");
+            let value = cookie_value(&set_cookie);
+
+            assert_eq!(rotated.open(value).expect("Session should be valid"), user);
+
+            let set_cookie = rotated.issue(&user).expect("Failed to issue session");
+            let value = cookie_value(&set_cookie);
+
+            assert!(matches!(
+                old.open(value),
+                Err(StatelessSessionError::UnknownKey { .. })
+            ));
+
+            // Flip a character of the payload or the nonce.
+            let index = value.find('.').expect("Cookie has a key id") + 1;
+            let replacement = if &value[index..=index] == "A" {
+                "B"
+            } else {
+                "A"
+            };
+
+            let mut tampered = value.to_string();
+            tampered.replace_range(index..=index, replacement);
+
+            assert!(matches!(
+                rotated.open(&tampered),
+                Err(StatelessSessionError::InvalidSignature | StatelessSessionError::Decrypt)
+            ));
+        }
+    }
+}
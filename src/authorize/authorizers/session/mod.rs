@@ -0,0 +1,13 @@
+mod impls;
+pub mod store;
+
+pub use impls::{
+    session::Session,
+    session_authorizer::{SessionAuthorizeError, SessionAuthorizer, SessionAuthorizerBuilder},
+    session_cookie::{SameSite, SessionCookie, SessionCookieError},
+    session_key::{SessionKey, SessionKeyRing},
+    stateless_session_authorizer::{
+        SessionProtection, StatelessSessionAuthorizer, StatelessSessionAuthorizerBuilder,
+        StatelessSessionError, StatelessSessionIssueError,
+    },
+};
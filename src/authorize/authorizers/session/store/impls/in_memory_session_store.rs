@@ -0,0 +1,119 @@
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    sync::{Arc, RwLock},
+    time::SystemTime,
+};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand::{rngs::OsRng, RngCore};
+
+use crate::authorize::session::{store::SessionStore, Session};
+
+/// Keeps sessions in memory. Clones share the same sessions.
+///
+/// Expired sessions are removed when they are used. Use [`InMemorySessionStore::retain`] to remove abandoned ones.
+#[derive(Debug)]
+pub struct InMemorySessionStore<P> {
+    sessions: Arc<RwLock<HashMap<String, Session<P>>>>,
+}
+
+impl<P> Clone for InMemorySessionStore<P> {
+    fn clone(&self) -> Self {
+        Self {
+            sessions: self.sessions.clone(),
+        }
+    }
+}
+
+impl<P> Default for InMemorySessionStore<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> InMemorySessionStore<P> {
+    pub fn new() -> Self {
+        Self {
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Starts a session for `principal` and returns its randomly generated id.
+    pub fn create(&self, principal: P) -> String {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+
+        let id = URL_SAFE_NO_PAD.encode(bytes);
+
+        self.insert(id.clone(), Session::new(principal));
+
+        id
+    }
+
+    pub fn insert(&self, id: impl Into<String>, session: Session<P>) {
+        self.sessions
+            .write()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert(id.into(), session);
+    }
+
+    /// Keeps only the sessions for which `keep` returns `true`.
+    pub fn retain(&self, mut keep: impl FnMut(&Session<P>) -> bool) {
+        self.sessions
+            .write()
+            .unwrap_or_else(|err| err.into_inner())
+            .retain(|_, session| keep(session));
+    }
+
+    pub fn len(&self) -> usize {
+        self.sessions
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<P> SessionStore for InMemorySessionStore<P>
+where
+    P: Clone + Send + Sync,
+{
+    type Principal = P;
+
+    type Error = Infallible;
+
+    async fn load(&self, id: &str) -> Result<Option<Session<P>>, Self::Error> {
+        Ok(self
+            .sessions
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .get(id)
+            .cloned())
+    }
+
+    async fn touch(&self, id: &str, last_seen_at: SystemTime) -> Result<(), Self::Error> {
+        if let Some(session) = self
+            .sessions
+            .write()
+            .unwrap_or_else(|err| err.into_inner())
+            .get_mut(id)
+        {
+            session.last_seen_at = last_seen_at;
+        }
+
+        Ok(())
+    }
+
+    async fn remove(&self, id: &str) -> Result<(), Self::Error> {
+        self.sessions
+            .write()
+            .unwrap_or_else(|err| err.into_inner())
+            .remove(id);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+mod impls;
+mod session_store;
+
+pub use impls::in_memory_session_store::InMemorySessionStore;
+pub use session_store::SessionStore;
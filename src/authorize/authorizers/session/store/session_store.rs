@@ -0,0 +1,24 @@
+use std::{future::Future, time::SystemTime};
+
+use crate::authorize::session::Session;
+
+pub trait SessionStore {
+    type Principal;
+
+    type Error;
+
+    /// Returns the session with the given id, or `None` if there is none.
+    fn load(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = Result<Option<Session<Self::Principal>>, Self::Error>> + Send;
+
+    /// Records that the session with the given id was used at `last_seen_at`.
+    fn touch(
+        &self,
+        id: &str,
+        last_seen_at: SystemTime,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    fn remove(&self, id: &str) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
@@ -0,0 +1,164 @@
+use std::borrow::Cow;
+
+use http::{header::COOKIE, HeaderMap};
+
+use crate::authorize::header::{bearer::BearerExtractor, HeaderExtractor};
+
+/// Extracts the value of a named cookie from the `Cookie` headers, as described in RFC 6265 section 5.4.
+///
+/// Can be used wherever a [`HeaderExtractor`] or a [`BearerExtractor`] is expected,
+/// e.g. to read an API key, a session id or a JWT from a cookie.
+#[derive(Debug)]
+pub struct CookieExtractor {
+    cookie_name: Cow<'static, str>,
+}
+
+impl CookieExtractor {
+    pub fn new(cookie_name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            cookie_name: cookie_name.into(),
+        }
+    }
+
+    pub fn cookie_name(&self) -> &str {
+        &self.cookie_name
+    }
+
+    /// Finds the cookie `name` in the value of a `Cookie` header.
+    ///
+    /// Surrounding double quotes are removed from the value.
+    pub fn extract_cookie<'a>(cookie: &'a str, name: &str) -> Option<&'a str> {
+        cookie.split(';').find_map(|pair| {
+            let (cookie_name, value) = pair.trim().split_once('=')?;
+
+            if cookie_name.trim() != name {
+                return None;
+            }
+
+            let value = value.trim();
+
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value);
+
+            Some(value)
+        })
+    }
+
+    fn extract<'a>(&self, headers: &'a HeaderMap) -> Result<&'a str, CookieError> {
+        let mut found = false;
+        let mut non_ascii = false;
+
+        // HTTP/2 clients may split cookies over several headers.
+        for cookie in headers.get_all(COOKIE) {
+            found = true;
+
+            // Skip the header, the cookie may still be in another one.
+            let Ok(cookie) = cookie.to_str() else {
+                non_ascii = true;
+                continue;
+            };
+
+            if let Some(value) = Self::extract_cookie(cookie, &self.cookie_name) {
+                if value.is_empty() {
+                    return Err(CookieError::Empty);
+                }
+
+                return Ok(value);
+            }
+        }
+
+        if !found {
+            return Err(CookieError::MissingHeader);
+        }
+
+        if non_ascii {
+            return Err(CookieError::Ascii);
+        }
+
+        Err(CookieError::Missing)
+    }
+}
+
+impl HeaderExtractor for CookieExtractor {
+    type Error = CookieError;
+
+    fn extract_header<'a>(&self, headers: &'a HeaderMap) -> Result<&'a str, Self::Error> {
+        self.extract(headers)
+    }
+}
+
+impl BearerExtractor for CookieExtractor {
+    type Error = CookieError;
+
+    fn extract_bearer<'a>(&self, headers: &'a HeaderMap) -> Result<&'a str, Self::Error> {
+        self.extract(headers)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CookieError {
+    #[error("Cookie header not found")]
+    MissingHeader,
+    #[error("Cookie header is not valid ascii")]
+    Ascii,
+    #[error("Cookie not found")]
+    Missing,
+    #[error("Cookie is empty")]
+    Empty,
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn cookies_are_found_across_headers() {
+        let extractor = CookieExtractor::new("session");
+
+        let mut headers = HeaderMap::new();
+
+        assert!(matches!(
+            extractor.extract_header(&headers),
+            Err(CookieError::MissingHeader)
+        ));
+
+        headers.append(COOKIE, HeaderValue::from_static("theme=dark; lang=en"));
+
+        assert!(matches!(
+            extractor.extract_header(&headers),
+            Err(CookieError::Missing)
+        ));
+
+        headers.append(
+            COOKIE,
+            HeaderValue::from_static("sessionid=1; session=\"abc\""),
+        );
+
+        assert_eq!(extractor.extract_header(&headers).unwrap(), "abc");
+    }
+
+    #[test]
+    fn non_ascii_headers_are_skipped() {
+        let extractor = CookieExtractor::new("session");
+
+        let mut headers = HeaderMap::new();
+
+        headers.append(
+            COOKIE,
+            HeaderValue::from_bytes("name=J\u{f6}rg".as_bytes()).expect("Valid header value"),
+        );
+
+        assert!(matches!(
+            extractor.extract_header(&headers),
+            Err(CookieError::Ascii)
+        ));
+
+        headers.append(COOKIE, HeaderValue::from_static("session=abc"));
+
+        assert_eq!(extractor.extract_header(&headers).unwrap(), "abc");
+    }
+}
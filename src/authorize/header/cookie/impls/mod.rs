@@ -0,0 +1 @@
+pub mod cookie_extractor;
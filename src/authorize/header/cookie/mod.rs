@@ -0,0 +1,3 @@
+mod impls;
+
+pub use impls::cookie_extractor::{CookieError, CookieExtractor};
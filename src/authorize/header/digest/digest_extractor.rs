@@ -0,0 +1,48 @@
+use http::HeaderMap;
+
+use super::DigestCredentials;
+
+pub trait DigestExtractor {
+    type Error;
+
+    fn extract_digest(&self, headers: &HeaderMap) -> Result<DigestCredentials, Self::Error>;
+}
+
+pub trait DigestExtractorExt: Sized + DigestExtractor {
+    fn map_err<Fn>(self, map_err: Fn) -> MapError<Self, Fn>;
+}
+
+impl<T> DigestExtractorExt for T
+where
+    T: Sized + DigestExtractor,
+{
+    fn map_err<Fn>(self, map_err: Fn) -> MapError<Self, Fn> {
+        MapError::new(self, map_err)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MapError<T, Fn> {
+    inner: T,
+    map_err: Fn,
+}
+
+impl<T, Fn> MapError<T, Fn> {
+    pub const fn new(inner: T, map_err: Fn) -> Self {
+        Self { inner, map_err }
+    }
+}
+
+impl<D, Fn, E> DigestExtractor for MapError<D, Fn>
+where
+    D: DigestExtractor + Sync,
+    Fn: FnOnce(D::Error) -> E + Clone + Sync,
+{
+    type Error = E;
+
+    fn extract_digest(&self, headers: &HeaderMap) -> Result<DigestCredentials, Self::Error> {
+        self.inner
+            .extract_digest(headers)
+            .map_err(self.map_err.clone())
+    }
+}
@@ -0,0 +1,169 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use http::{header::AUTHORIZATION, HeaderMap};
+
+use crate::authorize::header::{
+    digest::{digest_extractor::DigestExtractor, DigestAlgorithm, DigestCredentials},
+    header_extractor::HeaderExtractor,
+    impls::default_header_extractor::{DefaultHeaderError, DefaultHeaderExtractor},
+};
+
+#[derive(Debug)]
+pub struct DefaultDigestExtractor {
+    // This is not generic, because we have to make sure that the header name is always "Authorization"
+    header_extractor: DefaultHeaderExtractor,
+}
+
+impl DefaultDigestExtractor {
+    pub fn new() -> Self {
+        Self {
+            header_extractor: DefaultHeaderExtractor::new(Cow::from(AUTHORIZATION.as_str())),
+        }
+    }
+
+    /// Parses the comma-separated `name=value` pairs of a challenge response.
+    ///
+    /// Values are either tokens or quoted strings with backslash escapes.
+    fn parse_params(params: &str) -> Result<HashMap<String, String>, DefaultDigestError> {
+        let mut parsed = HashMap::new();
+        let mut chars = params.chars().peekable();
+
+        loop {
+            while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {}
+
+            if chars.peek().is_none() {
+                return Ok(parsed);
+            }
+
+            let name: String = std::iter::from_fn(|| chars.next_if(|c| *c != '=')).collect();
+
+            if chars.next() != Some('=') {
+                return Err(DefaultDigestError::Format);
+            }
+
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+            let mut value = String::new();
+
+            if chars.next_if_eq(&'"').is_some() {
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => value.push(chars.next().ok_or(DefaultDigestError::Format)?),
+                        Some(c) => value.push(c),
+                        None => return Err(DefaultDigestError::Format),
+                    }
+                }
+            } else {
+                value.extend(std::iter::from_fn(|| chars.next_if(|c| *c != ',')));
+                value = value.trim_end().to_string();
+            }
+
+            parsed.insert(name.trim().to_ascii_lowercase(), value);
+        }
+    }
+
+    pub fn parse(authorization: &str) -> Result<DigestCredentials, DefaultDigestError> {
+        let params = match authorization.split_once(' ') {
+            Some((scheme, params)) if scheme.eq_ignore_ascii_case("Digest") => params,
+            _ => return Err(DefaultDigestError::Format),
+        };
+
+        let mut params = Self::parse_params(params)?;
+
+        let mut required = |name: &'static str| {
+            params
+                .remove(name)
+                .ok_or(DefaultDigestError::Missing { param: name })
+        };
+
+        let username = required("username")?;
+        let realm = required("realm")?;
+        let nonce = required("nonce")?;
+        let uri = required("uri")?;
+        let response = required("response")?;
+
+        let algorithm = match params.remove("algorithm") {
+            Some(algorithm) => algorithm
+                .parse()
+                .map_err(|_| DefaultDigestError::Algorithm { algorithm })?,
+            None => DigestAlgorithm::Md5,
+        };
+
+        Ok(DigestCredentials {
+            username,
+            realm,
+            nonce,
+            uri,
+            response,
+            algorithm,
+            qop: params.remove("qop"),
+            nc: params.remove("nc"),
+            cnonce: params.remove("cnonce"),
+            opaque: params.remove("opaque"),
+        })
+    }
+}
+
+impl Default for DefaultDigestExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DigestExtractor for DefaultDigestExtractor {
+    type Error = DefaultDigestError;
+
+    fn extract_digest(&self, headers: &HeaderMap) -> Result<DigestCredentials, Self::Error> {
+        let authorization = self.header_extractor.extract_header(headers)?;
+
+        Self::parse(authorization)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DefaultDigestError {
+    #[error("Authorization header extraction error: {0}")]
+    Header(
+        #[source]
+        #[from]
+        DefaultHeaderError,
+    ),
+    #[error("Authorization header is not in the form: `Digest name=value, ...`")]
+    Format,
+    #[error("Digest parameter {param} not found")]
+    Missing { param: &'static str },
+    #[error("Unsupported digest algorithm: {algorithm}")]
+    Algorithm { algorithm: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digest_parameters_are_parsed() {
+        let credentials = DefaultDigestExtractor::parse(
+            r#"Digest username="Mufasa", realm="http-auth@example.org", uri="/dir/index.html", algorithm=SHA-256, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", nc=00000001, cnonce="f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ", qop=auth, response="753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#,
+        )
+        .expect("Valid digest");
+
+        assert_eq!(credentials.username, "Mufasa");
+        assert_eq!(credentials.algorithm, DigestAlgorithm::Sha256);
+        assert_eq!(credentials.nc.as_deref(), Some("00000001"));
+        assert_eq!(credentials.qop.as_deref(), Some("auth"));
+
+        let credentials = DefaultDigestExtractor::parse(
+            r#"Digest username="a \"quoted\" name", realm="r", nonce="n", uri="/", response="x""#,
+        )
+        .expect("Valid digest");
+
+        assert_eq!(credentials.username, r#"a "quoted" name"#);
+        assert_eq!(credentials.algorithm, DigestAlgorithm::Md5);
+
+        assert!(matches!(
+            DefaultDigestExtractor::parse(r#"Digest username="a", realm="r""#),
+            Err(DefaultDigestError::Missing { param: "nonce" })
+        ));
+    }
+}
@@ -0,0 +1,75 @@
+use std::str::FromStr;
+
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+/// The hash algorithms of HTTP Digest authentication, as defined in RFC 7616 section 3.3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DigestAlgorithm {
+    /// Only for clients that do not support anything else.
+    Md5,
+    Sha256,
+}
+
+impl DigestAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DigestAlgorithm::Md5 => "MD5",
+            DigestAlgorithm::Sha256 => "SHA-256",
+        }
+    }
+
+    /// Hashes the `:`-separated `parts` to lowercase hex.
+    pub fn hash(&self, parts: &[&str]) -> String {
+        fn hash<D: Digest>(parts: &[&str]) -> String {
+            let mut digest = D::new();
+
+            for (i, part) in parts.iter().enumerate() {
+                if i > 0 {
+                    digest.update(b":");
+                }
+
+                digest.update(part.as_bytes());
+            }
+
+            hex::encode(digest.finalize())
+        }
+
+        match self {
+            DigestAlgorithm::Md5 => hash::<Md5>(parts),
+            DigestAlgorithm::Sha256 => hash::<Sha256>(parts),
+        }
+    }
+}
+
+impl FromStr for DigestAlgorithm {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("MD5") {
+            return Ok(DigestAlgorithm::Md5);
+        }
+
+        if s.eq_ignore_ascii_case("SHA-256") {
+            return Ok(DigestAlgorithm::Sha256);
+        }
+
+        Err(())
+    }
+}
+
+/// The parameters of an `Authorization: Digest ...` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestCredentials {
+    pub username: String,
+    pub realm: String,
+    pub nonce: String,
+    pub uri: String,
+    pub response: String,
+    /// `MD5` if the client did not send an algorithm.
+    pub algorithm: DigestAlgorithm,
+    pub qop: Option<String>,
+    pub nc: Option<String>,
+    pub cnonce: Option<String>,
+    pub opaque: Option<String>,
+}
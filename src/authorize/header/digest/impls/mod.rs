@@ -0,0 +1,2 @@
+pub mod default_digest_extractor;
+pub mod digest_credentials;
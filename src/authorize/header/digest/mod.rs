@@ -0,0 +1,8 @@
+mod digest_extractor;
+mod impls;
+
+pub use digest_extractor::{DigestExtractor, DigestExtractorExt, MapError};
+pub use impls::{
+    default_digest_extractor::{DefaultDigestError, DefaultDigestExtractor},
+    digest_credentials::{DigestAlgorithm, DigestCredentials},
+};
@@ -1,5 +1,7 @@
 pub mod basic_auth;
 pub mod bearer;
+pub mod cookie;
+pub mod digest;
 mod header_extractor;
 mod impls;
 
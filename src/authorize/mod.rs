@@ -1,6 +1,13 @@
 mod authorizers;
 pub mod header;
+pub mod store;
 
 pub use authorizers::api_key;
 pub use authorizers::basic_auth;
+pub use authorizers::digest;
+pub use authorizers::hmac;
+pub use authorizers::introspection;
 pub use authorizers::jwt;
+pub use authorizers::rbac;
+pub use authorizers::scope;
+pub use authorizers::session;
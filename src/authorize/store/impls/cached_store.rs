@@ -0,0 +1,308 @@
+use std::{
+    future::Future,
+    num::NonZeroUsize,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+
+use crate::authorize::{
+    api_key::{ApiKeyRecord, ApiKeyStore},
+    basic_auth::{PasswordHash, UserLookup, UserStore},
+};
+
+/// `expires_at` is `None` if the time to live is too large to represent, in which case the entry never expires.
+#[derive(Debug)]
+enum CacheEntry<V> {
+    Found {
+        value: V,
+        expires_at: Option<Instant>,
+    },
+    Missing {
+        expires_at: Option<Instant>,
+    },
+}
+
+impl<V> CacheEntry<V> {
+    fn is_expired(&self, now: Instant) -> bool {
+        let expires_at = match self {
+            CacheEntry::Found { expires_at, .. } => expires_at,
+            CacheEntry::Missing { expires_at } => expires_at,
+        };
+
+        expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// Caches the results of a slow store, e.g. a database lookup.
+///
+/// Entries are keyed by a SHA-256 hash of the API key or the username.
+/// Errors of the wrapped store are never cached.
+///
+/// - Wrapping an [`ApiKeyStore`] gives an [`ApiKeyStore`].
+/// - Wrapping a [`UserLookup`] gives a [`UserStore`] that caches the password hashes and verifies them on every request,
+///   on the blocking thread pool of tokio. Unknown users are verified against the last found hash, so that they take as long as known ones.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let users = CachedStore::new(DatabaseUserLookup::new(pool))
+///     .capacity(NonZeroUsize::new(256).unwrap())
+///     .time_to_live_in_seconds(30)
+///     .negative_time_to_live_in_seconds(5);
+///
+/// let authorizer = DefaultBasicAuthAuthorizer::new(basic_auth_extractor, users);
+/// ```
+#[derive(Debug)]
+pub struct CachedStore<S, V> {
+    store: S,
+    time_to_live: Duration,
+    negative_time_to_live: Option<Duration>,
+    cache: Mutex<LruCache<[u8; 32], CacheEntry<V>>>,
+    /// Verified for unknown users.
+    dummy: Mutex<Option<V>>,
+}
+
+impl<S, V> CachedStore<S, V> {
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            time_to_live: Duration::from_secs(60),
+            negative_time_to_live: None,
+            cache: Mutex::new(LruCache::new(
+                NonZeroUsize::new(1024).expect("1024 is not zero"),
+            )),
+            dummy: Mutex::new(None),
+        }
+    }
+
+    pub fn capacity(mut self, capacity: NonZeroUsize) -> Self {
+        self.cache
+            .get_mut()
+            .unwrap_or_else(|err| err.into_inner())
+            .resize(capacity);
+        self
+    }
+
+    pub fn time_to_live_in_seconds(mut self, time_to_live_in_seconds: u64) -> Self {
+        self.time_to_live = Duration::from_secs(time_to_live_in_seconds);
+        self
+    }
+
+    /// Caches unknown API keys and users for the given duration.
+    pub fn negative_time_to_live_in_seconds(
+        mut self,
+        negative_time_to_live_in_seconds: u64,
+    ) -> Self {
+        self.negative_time_to_live = Some(Duration::from_secs(negative_time_to_live_in_seconds));
+        self
+    }
+
+    /// Removes all cached entries.
+    pub fn clear(&self) {
+        self.cache
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clear();
+    }
+}
+
+impl<S, V> CachedStore<S, V>
+where
+    V: Clone,
+{
+    fn lookup(&self, key: &[u8; 32]) -> Option<Option<V>> {
+        let mut cache = self.cache.lock().unwrap_or_else(|err| err.into_inner());
+
+        let entry = cache.get(key)?;
+
+        if entry.is_expired(Instant::now()) {
+            cache.pop(key);
+
+            return None;
+        }
+
+        match entry {
+            CacheEntry::Found { value, .. } => Some(Some(value.clone())),
+            CacheEntry::Missing { .. } => Some(None),
+        }
+    }
+
+    fn store(&self, key: [u8; 32], value: Option<V>) {
+        let now = Instant::now();
+
+        let entry = match value {
+            Some(value) => CacheEntry::Found {
+                value,
+                expires_at: now.checked_add(self.time_to_live),
+            },
+            None => match self.negative_time_to_live {
+                Some(negative_time_to_live) => CacheEntry::Missing {
+                    expires_at: now.checked_add(negative_time_to_live),
+                },
+                None => return,
+            },
+        };
+
+        self.cache
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .put(key, entry);
+    }
+
+    async fn get_or_load<F, Fut, E>(&self, key: &str, load: F) -> Result<Option<V>, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Option<V>, E>>,
+    {
+        let key: [u8; 32] = Sha256::digest(key.as_bytes()).into();
+
+        if let Some(value) = self.lookup(&key) {
+            return Ok(value);
+        }
+
+        let value = load().await?;
+
+        self.store(key, value.clone());
+
+        Ok(value)
+    }
+}
+
+impl<S> ApiKeyStore for CachedStore<S, ApiKeyRecord>
+where
+    S: ApiKeyStore + Send + Sync,
+{
+    type Error = CachedStoreError<S::Error>;
+
+    async fn find_api_key(&self, api_key: &str) -> Result<Option<ApiKeyRecord>, Self::Error> {
+        self.get_or_load(api_key, || self.store.find_api_key(api_key))
+            .await
+            .map_err(CachedStoreError::Store)
+    }
+}
+
+impl<L> UserStore for CachedStore<L, PasswordHash>
+where
+    L: UserLookup + Send + Sync,
+{
+    type Error = CachedStoreError<L::Error>;
+
+    async fn verify_user(&self, username: &str, password: &str) -> Result<bool, Self::Error> {
+        let password_hash = self
+            .get_or_load(username, || self.store.find_password_hash(username))
+            .await
+            .map_err(CachedStoreError::Store)?;
+
+        let Some(password_hash) = password_hash else {
+            let dummy = self
+                .dummy
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .clone();
+
+            if let Some(dummy) = dummy {
+                dummy.spawn_verify(password).await;
+            }
+
+            return Ok(false);
+        };
+
+        *self.dummy.lock().unwrap_or_else(|err| err.into_inner()) = Some(password_hash.clone());
+
+        Ok(password_hash.spawn_verify(password).await)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CachedStoreError<S> {
+    #[error("Store error: {0}")]
+    Store(#[source] S),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct CountingStore {
+        lookups: AtomicUsize,
+    }
+
+    impl ApiKeyStore for CountingStore {
+        type Error = Infallible;
+
+        async fn find_api_key(&self, api_key: &str) -> Result<Option<ApiKeyRecord>, Self::Error> {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+
+            Ok((api_key == "valid").then(|| ApiKeyRecord::new("user-1", "valid")))
+        }
+    }
+
+    #[tokio::test]
+    async fn lookups_are_cached() {
+        let store = CachedStore::new(CountingStore::default()).negative_time_to_live_in_seconds(60);
+
+        for _ in 0..3 {
+            assert!(store.find_api_key("valid").await.unwrap().is_some());
+            assert!(store.find_api_key("invalid").await.unwrap().is_none());
+        }
+
+        assert_eq!(store.store.lookups.load(Ordering::SeqCst), 2);
+
+        store.clear();
+
+        assert!(store.find_api_key("valid").await.unwrap().is_some());
+        assert_eq!(store.store.lookups.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn huge_time_to_live_never_expires() {
+        let store = CachedStore::new(CountingStore::default())
+            .time_to_live_in_seconds(u64::MAX)
+            .negative_time_to_live_in_seconds(u64::MAX);
+
+        for _ in 0..3 {
+            assert!(store.find_api_key("valid").await.unwrap().is_some());
+            assert!(store.find_api_key("invalid").await.unwrap().is_none());
+        }
+
+        assert_eq!(store.store.lookups.load(Ordering::SeqCst), 2);
+    }
+
+    struct PasswordLookup {
+        password_hash: PasswordHash,
+    }
+
+    impl UserLookup for PasswordLookup {
+        type Error = Infallible;
+
+        async fn find_password_hash(
+            &self,
+            username: &str,
+        ) -> Result<Option<PasswordHash>, Self::Error> {
+            Ok((username == "user").then(|| self.password_hash.clone()))
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_users_are_verified_against_a_dummy() {
+        let store = CachedStore::new(PasswordLookup {
+            password_hash: PasswordHash::bcrypt("password", 4).expect("Failed to hash"),
+        });
+
+        assert!(!store.verify_user("unknown", "password").await.unwrap());
+        assert!(store.verify_user("user", "password").await.unwrap());
+        assert!(!store.verify_user("user", "wrong").await.unwrap());
+
+        assert!(store.dummy.lock().unwrap().is_some());
+        assert!(!store.verify_user("unknown", "password").await.unwrap());
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::{Arc, RwLock};
+
+use crate::authorize::{
+    api_key::{ApiKeyRecord, ApiKeyStore},
+    basic_auth::UserStore,
+};
+
+/// A store that can be replaced while the service runs.
+///
+/// Clones share the same store. Requests that already loaded the old store keep using it.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let api_keys = HotReload::new(parse_api_keys("key-1")?);
+///
+/// let authorizer = DefaultApiKeyAuthorizer::new(header_extractor, api_keys.clone());
+///
+/// api_keys.store(parse_api_keys("key-2")?);
+/// ```
+#[derive(Debug)]
+pub struct HotReload<T> {
+    current: Arc<RwLock<Arc<T>>>,
+}
+
+impl<T> HotReload<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(value))),
+        }
+    }
+
+    /// Returns the current store.
+    pub fn load(&self) -> Arc<T> {
+        self.current
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    /// Replaces the current store.
+    pub fn store(&self, value: T) {
+        *self.current.write().unwrap_or_else(|err| err.into_inner()) = Arc::new(value);
+    }
+}
+
+impl<T> Clone for HotReload<T> {
+    fn clone(&self) -> Self {
+        Self {
+            current: self.current.clone(),
+        }
+    }
+}
+
+impl<T> UserStore for HotReload<T>
+where
+    T: UserStore + Send + Sync,
+{
+    type Error = T::Error;
+
+    async fn verify_user(&self, username: &str, password: &str) -> Result<bool, Self::Error> {
+        let current = self.load();
+
+        current.verify_user(username, password).await
+    }
+}
+
+impl<T> ApiKeyStore for HotReload<T>
+where
+    T: ApiKeyStore + Send + Sync,
+{
+    type Error = T::Error;
+
+    async fn find_api_key(&self, api_key: &str) -> Result<Option<ApiKeyRecord>, Self::Error> {
+        let current = self.load();
+
+        current.find_api_key(api_key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use http::Request;
+
+    use crate::{
+        authorize::{
+            api_key::{ApiKey, DefaultApiKeyAuthorizer},
+            header::DefaultHeaderExtractor,
+        },
+        extract::Extractor,
+    };
+
+    use super::*;
+
+    fn api_keys(key: &'static str) -> HashMap<ApiKey, ApiKeyRecord> {
+        HashMap::from([(ApiKey::new(key), ApiKeyRecord::new("user-1", "ci"))])
+    }
+
+    fn parts(api_key: &str) -> http::request::Parts {
+        Request::builder()
+            .header("x-api-key", api_key)
+            .body(())
+            .expect("Valid request")
+            .into_parts()
+            .0
+    }
+
+    #[tokio::test]
+    async fn stored_values_reach_a_running_authorizer() {
+        let api_keys_store = HotReload::new(api_keys("old"));
+
+        let authorizer = DefaultApiKeyAuthorizer::new(
+            DefaultHeaderExtractor::new("x-api-key"),
+            api_keys_store.clone(),
+        );
+
+        assert!(authorizer.extract(&parts("old")).await.is_ok());
+
+        api_keys_store.store(api_keys("new"));
+
+        assert!(authorizer.extract(&parts("old")).await.is_err());
+        assert!(authorizer.extract(&parts("new")).await.is_ok());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod cached_store;
+pub mod hot_reload;
+pub mod watched_file;
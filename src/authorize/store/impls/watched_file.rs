@@ -0,0 +1,298 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use sha2::{Digest, Sha256};
+use tokio::sync::oneshot;
+
+use crate::authorize::{
+    api_key::{ApiKeyRecord, ApiKeyStore},
+    basic_auth::UserStore,
+};
+
+use super::hot_reload::HotReload;
+
+/// A store loaded from a file that is reloaded when the file changes.
+///
+/// The file is read in the background every poll interval and reloaded when the SHA-256 of its contents changes.
+/// If a reload fails or `parse` panics, the error is logged and the previous store is kept.
+/// The background task is stopped when the [`WatchedFile`] is dropped.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let api_keys = WatchedFile::new("api_keys.txt", 5, parse_api_keys).await?;
+///
+/// let authorizer = DefaultApiKeyAuthorizer::new(header_extractor, api_keys);
+/// ```
+#[derive(Debug)]
+pub struct WatchedFile<T> {
+    store: HotReload<T>,
+    _cancellation_tx: oneshot::Sender<()>,
+}
+
+impl<T> WatchedFile<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Loads the file and starts watching it.
+    pub async fn new<P, E>(
+        path: impl Into<PathBuf>,
+        poll_interval_in_seconds: u64,
+        parse: P,
+    ) -> Result<Self, WatchedFileError<E>>
+    where
+        P: Fn(&str) -> Result<T, E> + Send + Sync + 'static,
+        E: std::fmt::Debug + Send + 'static,
+    {
+        let path = path.into();
+
+        let contents = read(&path).await?;
+        let version = version(&contents);
+        let value = load(&contents, &parse)?;
+
+        let store = HotReload::new(value);
+
+        let (tx, rx) = oneshot::channel();
+
+        tokio::spawn(Self::background_reload_loop(
+            path,
+            Duration::from_secs(poll_interval_in_seconds),
+            Some(version),
+            parse,
+            store.clone(),
+            rx,
+        ));
+
+        Ok(Self {
+            store,
+            _cancellation_tx: tx,
+        })
+    }
+
+    async fn background_reload_loop<P, E>(
+        path: PathBuf,
+        poll_interval: Duration,
+        mut last_version: Option<[u8; 32]>,
+        parse: P,
+        store: HotReload<T>,
+        mut cancellation_rx: oneshot::Receiver<()>,
+    ) where
+        P: Fn(&str) -> Result<T, E> + Send + Sync + 'static,
+        E: std::fmt::Debug + Send + 'static,
+    {
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(poll_interval) => {
+                    let contents = read(&path).await;
+                    let version = contents.as_deref().ok().map(version);
+
+                    if version == last_version {
+                        continue;
+                    }
+
+                    last_version = version;
+
+                    match contents.and_then(|contents| load(&contents, &parse)) {
+                        Ok(value) => {
+                            tracing::debug!(?path, "Reloaded file");
+
+                            store.store(value);
+                        }
+                        Err(err) => {
+                            tracing::error!(?err, ?path, "Failed to reload file");
+                        }
+                    }
+                }
+                _ = &mut cancellation_rx => {
+                    break;
+                }
+            }
+        }
+
+        tracing::debug!(?path, "Background reload loop terminated");
+    }
+}
+
+impl<T> WatchedFile<T> {
+    /// The underlying store, which is replaced on every reload.
+    pub fn store(&self) -> &HotReload<T> {
+        &self.store
+    }
+}
+
+/// The SHA-256 of the contents, which changes with every rewrite regardless of the file's metadata.
+fn version(contents: &str) -> [u8; 32] {
+    Sha256::digest(contents).into()
+}
+
+async fn read<E>(path: &Path) -> Result<String, WatchedFileError<E>> {
+    tokio::fs::read_to_string(path)
+        .await
+        .map_err(WatchedFileError::Read)
+}
+
+fn load<T, E, P>(contents: &str, parse: &P) -> Result<T, WatchedFileError<E>>
+where
+    P: Fn(&str) -> Result<T, E>,
+{
+    catch_unwind(AssertUnwindSafe(|| parse(contents)))
+        .map_err(|_| WatchedFileError::Panic)?
+        .map_err(WatchedFileError::Parse)
+}
+
+impl<T> UserStore for WatchedFile<T>
+where
+    T: UserStore + Send + Sync,
+{
+    type Error = T::Error;
+
+    async fn verify_user(&self, username: &str, password: &str) -> Result<bool, Self::Error> {
+        self.store.verify_user(username, password).await
+    }
+}
+
+impl<T> ApiKeyStore for WatchedFile<T>
+where
+    T: ApiKeyStore + Send + Sync,
+{
+    type Error = T::Error;
+
+    async fn find_api_key(&self, api_key: &str) -> Result<Option<ApiKeyRecord>, Self::Error> {
+        self.store.find_api_key(api_key).await
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WatchedFileError<E> {
+    #[error("Failed to read file: {0}")]
+    Read(#[source] std::io::Error),
+    #[error("Failed to parse file: {0}")]
+    Parse(#[source] E),
+    #[error("Parser panicked")]
+    Panic,
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Request;
+
+    use crate::{
+        authorize::{
+            api_key::{parse_api_keys, DefaultApiKeyAuthorizer},
+            header::DefaultHeaderExtractor,
+        },
+        extract::Extractor,
+    };
+
+    use super::*;
+
+    fn api_keys(key: &str) -> String {
+        format!(r#"[{{ "key": "{key}", "owner_id": "user-1", "name": "ci" }}]"#)
+    }
+
+    fn parts(api_key: &str) -> http::request::Parts {
+        Request::builder()
+            .header("x-api-key", api_key)
+            .body(())
+            .expect("Valid request")
+            .into_parts()
+            .0
+    }
+
+    #[tokio::test]
+    async fn file_changes_reach_a_running_authorizer() {
+        let path = std::env::temp_dir().join(format!("api-keys-{}.json", std::process::id()));
+
+        tokio::fs::write(&path, api_keys("old"))
+            .await
+            .expect("Failed to write file");
+
+        let api_keys_file = WatchedFile::new(&path, 1, parse_api_keys)
+            .await
+            .expect("Failed to load file");
+
+        let authorizer =
+            DefaultApiKeyAuthorizer::new(DefaultHeaderExtractor::new("x-api-key"), api_keys_file);
+
+        assert!(authorizer.extract(&parts("old")).await.is_ok());
+
+        tokio::fs::write(&path, "not json")
+            .await
+            .expect("Failed to write file");
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+
+        assert!(authorizer.extract(&parts("old")).await.is_ok());
+
+        tokio::fs::write(&path, api_keys("rotated"))
+            .await
+            .expect("Failed to write file");
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+
+        assert!(authorizer.extract(&parts("old")).await.is_err());
+        assert!(authorizer.extract(&parts("rotated")).await.is_ok());
+
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+
+    #[tokio::test]
+    async fn rewrites_with_the_same_length_and_modification_time_are_reloaded() {
+        let path = std::env::temp_dir().join(format!("same-length-{}.json", std::process::id()));
+
+        tokio::fs::write(&path, api_keys("old"))
+            .await
+            .expect("Failed to write file");
+
+        let modified = tokio::fs::metadata(&path)
+            .await
+            .and_then(|metadata| metadata.modified())
+            .expect("Failed to read modification time");
+
+        let api_keys_file = WatchedFile::new(&path, 1, parse_api_keys)
+            .await
+            .expect("Failed to load file");
+
+        let authorizer =
+            DefaultApiKeyAuthorizer::new(DefaultHeaderExtractor::new("x-api-key"), api_keys_file);
+
+        tokio::fs::write(&path, api_keys("new"))
+            .await
+            .expect("Failed to write file");
+
+        // Same length and modification time as before, so only the contents differ.
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(modified))
+            .expect("Failed to set modification time");
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+
+        assert!(authorizer.extract(&parts("old")).await.is_err());
+        assert!(authorizer.extract(&parts("new")).await.is_ok());
+
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+
+    #[tokio::test]
+    async fn parser_panics_are_returned_as_errors() {
+        let path = std::env::temp_dir().join(format!("panic-{}.txt", std::process::id()));
+
+        tokio::fs::write(&path, "")
+            .await
+            .expect("Failed to write file");
+
+        let result = WatchedFile::new(&path, 1, |_: &str| -> Result<(), String> {
+            panic!("Oh no")
+        })
+        .await;
+
+        assert!(matches!(result, Err(WatchedFileError::Panic)));
+
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+}
@@ -0,0 +1,7 @@
+mod impls;
+
+pub use impls::{
+    cached_store::{CachedStore, CachedStoreError},
+    hot_reload::HotReload,
+    watched_file::{WatchedFile, WatchedFileError},
+};
@@ -0,0 +1,39 @@
+use tower::Layer;
+
+use super::service::BodyExtensionService;
+
+#[derive(Debug, Clone)]
+pub struct BodyExtensionLayer<Ex> {
+    extractor: Ex,
+    limit: usize,
+}
+
+impl<Ex> BodyExtensionLayer<Ex> {
+    pub fn new(extractor: Ex, limit: usize) -> Self {
+        Self { extractor, limit }
+    }
+}
+
+impl<S, Ex> Layer<S> for BodyExtensionLayer<Ex>
+where
+    Ex: Clone,
+{
+    type Service = BodyExtensionService<S, Ex>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        BodyExtensionService::new(service, self.extractor.clone(), self.limit)
+    }
+}
+
+pub trait BodyExtensionLayerExt: Sized {
+    fn body_extension_layer(self, limit: usize) -> BodyExtensionLayer<Self>;
+}
+
+impl<T> BodyExtensionLayerExt for T
+where
+    T: Sized + Clone,
+{
+    fn body_extension_layer(self, limit: usize) -> BodyExtensionLayer<Self> {
+        BodyExtensionLayer::new(self, limit)
+    }
+}
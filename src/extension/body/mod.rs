@@ -0,0 +1,5 @@
+mod layer;
+mod service;
+
+pub use layer::{BodyExtensionLayer, BodyExtensionLayerExt};
+pub use service::{BodyExtensionService, BufferBodyError};
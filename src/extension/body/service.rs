@@ -0,0 +1,208 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use http::Request;
+use http_body::Body;
+use http_body_util::{BodyExt, LengthLimitError, Limited};
+use tower::Service;
+
+use crate::extract::{BufferedBody, Extractor, SealedExtracted};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, Clone)]
+pub struct BodyExtensionService<S, Ex> {
+    service: S,
+    extractor: Ex,
+    limit: usize,
+}
+
+impl<S, Ex> BodyExtensionService<S, Ex> {
+    pub fn new(service: S, extractor: Ex, limit: usize) -> Self {
+        Self {
+            service,
+            extractor,
+            limit,
+        }
+    }
+}
+
+impl<S, Ex, B> Service<Request<B>> for BodyExtensionService<S, Ex>
+where
+    Ex: Extractor + Clone + Send + 'static,
+    S: Service<Request<B>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Response: From<Ex::Error> + From<BufferBodyError>,
+    B: Body + From<Bytes> + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let mut service = self.service.clone();
+        let extractor = self.extractor.clone();
+        let limit = self.limit;
+
+        Box::pin(async move {
+            let (mut parts, body) = request.into_parts();
+
+            let bytes = match Limited::new(body, limit).collect().await {
+                Ok(collected) => collected.to_bytes(),
+                Err(err) if err.is::<LengthLimitError>() => {
+                    return Ok(From::from(BufferBodyError::TooLarge { limit }))
+                }
+                Err(err) => return Ok(From::from(BufferBodyError::Read(err))),
+            };
+
+            parts.extensions.insert(BufferedBody(bytes.clone()));
+
+            let extracted = match extractor.extract(&parts).await {
+                Ok(extracted) => extracted,
+                Err(err) => return Ok(From::from(err)),
+            };
+
+            parts.extensions.remove::<BufferedBody>();
+            parts.extensions.insert(SealedExtracted(extracted));
+
+            service
+                .call(Request::from_parts(parts, B::from(bytes)))
+                .await
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BufferBodyError {
+    #[error("Body exceeds the limit of {limit} bytes")]
+    TooLarge { limit: usize },
+    #[error("Body read error: {0}")]
+    Read(#[source] BoxError),
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use axum::response::{IntoResponse, Response};
+    use http::StatusCode;
+
+    use super::BufferBodyError;
+
+    impl IntoResponse for BufferBodyError {
+        fn into_response(self) -> Response {
+            tracing::warn!(err = %self, "Failed to buffer body");
+
+            match self {
+                BufferBodyError::TooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE.into_response(),
+                BufferBodyError::Read(_) => StatusCode::BAD_REQUEST.into_response(),
+            }
+        }
+    }
+
+    impl From<BufferBodyError> for Response {
+        fn from(value: BufferBodyError) -> Self {
+            value.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use http_body_util::Full;
+    use tower::{service_fn, ServiceExt};
+
+    use crate::error::InfallibleError;
+
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct BodyLengthExtractor;
+
+    impl Extractor for BodyLengthExtractor {
+        type Extracted = usize;
+
+        type Error = InfallibleError;
+
+        async fn extract(&self, parts: &http::request::Parts) -> Result<usize, Self::Error> {
+            Ok(BufferedBody::from_parts(parts).map_or(0, |body| body.len()))
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum TestResponse {
+        Ok { extracted: usize, body: Bytes },
+        TooLarge,
+        Other,
+    }
+
+    impl From<InfallibleError> for TestResponse {
+        fn from(_: InfallibleError) -> Self {
+            TestResponse::Other
+        }
+    }
+
+    impl From<BufferBodyError> for TestResponse {
+        fn from(value: BufferBodyError) -> Self {
+            match value {
+                BufferBodyError::TooLarge { .. } => TestResponse::TooLarge,
+                BufferBodyError::Read(_) => TestResponse::Other,
+            }
+        }
+    }
+
+    async fn call(limit: usize, body: &'static [u8]) -> TestResponse {
+        let inner = service_fn(|request: Request<Full<Bytes>>| async move {
+            let extracted = **request
+                .extensions()
+                .get::<SealedExtracted<usize>>()
+                .expect("Extracted extension is missing");
+
+            let body = request
+                .into_body()
+                .collect()
+                .await
+                .expect("Infallible body")
+                .to_bytes();
+
+            Ok::<_, Infallible>(TestResponse::Ok { extracted, body })
+        });
+
+        let request = Request::new(Full::new(Bytes::from_static(body)));
+
+        BodyExtensionService::new(inner, BodyLengthExtractor, limit)
+            .oneshot(request)
+            .await
+            .expect("Infallible service")
+    }
+
+    #[tokio::test]
+    async fn body_is_exposed_and_reassembled() {
+        let response = call(16, b"payload").await;
+
+        assert_eq!(
+            response,
+            TestResponse::Ok {
+                extracted: 7,
+                body: Bytes::from_static(b"payload"),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn body_over_limit_is_rejected() {
+        let response = call(4, b"payload").await;
+
+        assert_eq!(response, TestResponse::TooLarge);
+    }
+}
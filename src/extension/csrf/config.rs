@@ -0,0 +1,198 @@
+use std::{borrow::Cow, time::Duration};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use http::{header, HeaderMap, HeaderName, Method, Uri};
+use rand::{rngs::OsRng, RngCore};
+use subtle::ConstantTimeEq;
+
+use crate::authorize::{
+    header::{cookie::CookieExtractor, HeaderExtractor},
+    session::{SameSite, SessionCookie, SessionCookieError},
+};
+
+use super::service::CsrfError;
+
+/// The CSRF token of the current request, inserted into the request extensions by the [`CsrfLayer`](super::CsrfLayer).
+///
+/// Clients send it back in the CSRF header on unsafe requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsrfToken(pub(crate) String);
+
+impl CsrfToken {
+    pub(crate) fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+
+        Self(URL_SAFE_NO_PAD.encode(bytes))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Configures CSRF protection with double-submit cookies and an origin allow-list.
+///
+/// On safe requests (`GET`, `HEAD`, `OPTIONS`, `TRACE`) a token cookie is issued if the client has none.
+/// Unsafe requests must echo the cookie's value in the CSRF header,
+/// and their `Origin` (or `Referer`) must be allowed.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let csrf_layer = CsrfConfig::new()
+///     .allowed_origin("https://app.example.com")
+///     .header_name(HeaderName::from_static("x-xsrf-token"))
+///     .csrf_layer();
+/// ```
+#[derive(Debug)]
+pub struct CsrfConfig {
+    cookie: SessionCookie,
+    cookie_extractor: CookieExtractor,
+    max_age: Duration,
+    header_name: HeaderName,
+    allowed_origins: Vec<Cow<'static, str>>,
+    require_origin: bool,
+}
+
+impl Default for CsrfConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CsrfConfig {
+    /// Defaults to a `csrf_token` cookie, valid for one day, and an `x-csrf-token` header.
+    pub fn new() -> Self {
+        Self::with_cookie(
+            SessionCookie::new("csrf_token")
+                .expect("`csrf_token` is a valid cookie name")
+                .http_only(false)
+                .same_site(Some(SameSite::Strict)),
+        )
+    }
+
+    /// The token cookie must not be `HttpOnly`, so that scripts can copy it into the CSRF header.
+    pub fn with_cookie(cookie: SessionCookie) -> Self {
+        let cookie_extractor = CookieExtractor::new(cookie.name().to_string());
+
+        Self {
+            cookie,
+            cookie_extractor,
+            max_age: Duration::from_secs(24 * 60 * 60),
+            header_name: HeaderName::from_static("x-csrf-token"),
+            allowed_origins: Vec::new(),
+            require_origin: false,
+        }
+    }
+
+    pub fn max_age_in_seconds(mut self, max_age_in_seconds: u64) -> Self {
+        self.max_age = Duration::from_secs(max_age_in_seconds);
+        self
+    }
+
+    pub fn header_name(mut self, header_name: HeaderName) -> Self {
+        self.header_name = header_name;
+        self
+    }
+
+    /// Allows unsafe requests from `origin`, e.g. `https://app.example.com`.
+    ///
+    /// If no origin is allowed, origins are not checked.
+    pub fn allowed_origin(mut self, origin: impl Into<Cow<'static, str>>) -> Self {
+        self.allowed_origins.push(origin.into());
+        self
+    }
+
+    /// Rejects unsafe requests that have neither an `Origin` nor a `Referer` header.
+    pub fn require_origin(mut self, require_origin: bool) -> Self {
+        self.require_origin = require_origin;
+        self
+    }
+
+    pub(crate) fn is_safe(method: &Method) -> bool {
+        matches!(
+            *method,
+            Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+        )
+    }
+
+    pub(crate) fn token(&self, headers: &HeaderMap) -> Option<CsrfToken> {
+        self.cookie_extractor
+            .extract_header(headers)
+            .ok()
+            .map(|token| CsrfToken(token.to_string()))
+    }
+
+    pub(crate) fn set_cookie(
+        &self,
+        token: &CsrfToken,
+    ) -> Result<http::HeaderValue, SessionCookieError> {
+        self.cookie.set_cookie(token.as_str(), self.max_age)
+    }
+
+    pub(crate) fn verify(&self, headers: &HeaderMap) -> Result<CsrfToken, CsrfError> {
+        self.verify_origin(headers)?;
+
+        let cookie = self.token(headers).ok_or(CsrfError::MissingCookie)?;
+
+        let header = headers
+            .get(&self.header_name)
+            .ok_or(CsrfError::MissingHeader)?;
+
+        if !bool::from(cookie.as_str().as_bytes().ct_eq(header.as_bytes())) {
+            return Err(CsrfError::Mismatch);
+        }
+
+        Ok(cookie)
+    }
+
+    fn verify_origin(&self, headers: &HeaderMap) -> Result<(), CsrfError> {
+        if self.allowed_origins.is_empty() {
+            return Ok(());
+        }
+
+        let origin = match headers.get(header::ORIGIN) {
+            Some(origin) => Some(
+                origin
+                    .to_str()
+                    .map_err(|_| CsrfError::MalformedOrigin)?
+                    .to_string(),
+            ),
+            None => match headers.get(header::REFERER) {
+                Some(referer) => Some(referer_origin(referer.to_str().ok())?),
+                None => None,
+            },
+        };
+
+        let Some(origin) = origin else {
+            if self.require_origin {
+                return Err(CsrfError::MissingOrigin);
+            }
+
+            return Ok(());
+        };
+
+        if self
+            .allowed_origins
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(&origin))
+        {
+            return Ok(());
+        }
+
+        Err(CsrfError::Origin { origin })
+    }
+}
+
+/// Reduces a `Referer` to its `scheme://authority` origin.
+fn referer_origin(referer: Option<&str>) -> Result<String, CsrfError> {
+    let uri = referer
+        .and_then(|referer| referer.parse::<Uri>().ok())
+        .ok_or(CsrfError::MalformedOrigin)?;
+
+    match (uri.scheme_str(), uri.authority()) {
+        (Some(scheme), Some(authority)) => Ok(format!("{scheme}://{authority}")),
+        _ => Err(CsrfError::MalformedOrigin),
+    }
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use tower::Layer;
+
+use super::{config::CsrfConfig, service::CsrfService};
+
+#[derive(Debug, Clone)]
+pub struct CsrfLayer {
+    config: Arc<CsrfConfig>,
+}
+
+impl CsrfLayer {
+    pub fn new(config: CsrfConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+        }
+    }
+}
+
+impl<S> Layer<S> for CsrfLayer {
+    type Service = CsrfService<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        CsrfService::new(service, self.config.clone())
+    }
+}
+
+pub trait CsrfLayerExt: Sized {
+    fn csrf_layer(self) -> CsrfLayer;
+}
+
+impl CsrfLayerExt for CsrfConfig {
+    fn csrf_layer(self) -> CsrfLayer {
+        CsrfLayer::new(self)
+    }
+}
@@ -0,0 +1,7 @@
+mod config;
+mod layer;
+mod service;
+
+pub use config::{CsrfConfig, CsrfToken};
+pub use layer::{CsrfLayer, CsrfLayerExt};
+pub use service::{CsrfError, CsrfService};
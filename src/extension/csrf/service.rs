@@ -0,0 +1,203 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use http::{header::SET_COOKIE, Request, Response};
+use tower::Service;
+
+use super::config::{CsrfConfig, CsrfToken};
+
+#[derive(Debug, Clone)]
+pub struct CsrfService<S> {
+    service: S,
+    config: Arc<CsrfConfig>,
+}
+
+impl<S> CsrfService<S> {
+    pub fn new(service: S, config: Arc<CsrfConfig>) -> Self {
+        Self { service, config }
+    }
+}
+
+impl<S, B, ResBody> Service<Request<B>> for CsrfService<S>
+where
+    S: Service<Request<B>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Response: From<CsrfError>,
+    B: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        let mut service = self.service.clone();
+        let config = self.config.clone();
+
+        Box::pin(async move {
+            if !CsrfConfig::is_safe(request.method()) {
+                let token = match config.verify(request.headers()) {
+                    Ok(token) => token,
+                    Err(err) => return Ok(From::from(err)),
+                };
+
+                request.extensions_mut().insert(token);
+
+                return service.call(request).await;
+            }
+
+            let (token, issued) = match config.token(request.headers()) {
+                Some(token) => (token, false),
+                None => (CsrfToken::generate(), true),
+            };
+
+            request.extensions_mut().insert(token.clone());
+
+            let mut response = service.call(request).await?;
+
+            if issued {
+                match config.set_cookie(&token) {
+                    Ok(set_cookie) => {
+                        response.headers_mut().append(SET_COOKIE, set_cookie);
+                    }
+                    Err(err) => {
+                        tracing::error!(%err, "Failed to set CSRF cookie");
+                    }
+                }
+            }
+
+            Ok(response)
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CsrfError {
+    #[error("Origin not allowed: {origin}")]
+    Origin { origin: String },
+    #[error("Malformed Origin or Referer header")]
+    MalformedOrigin,
+    #[error("Origin and Referer headers not found")]
+    MissingOrigin,
+    #[error("CSRF cookie not found")]
+    MissingCookie,
+    #[error("CSRF header not found")]
+    MissingHeader,
+    #[error("CSRF header does not match the CSRF cookie")]
+    Mismatch,
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use axum::response::{IntoResponse, Response};
+    use http::StatusCode;
+
+    use super::CsrfError;
+
+    impl IntoResponse for CsrfError {
+        fn into_response(self) -> Response {
+            tracing::warn!(err = %self, "Forbidden");
+
+            StatusCode::FORBIDDEN.into_response()
+        }
+    }
+
+    impl From<CsrfError> for Response {
+        fn from(value: CsrfError) -> Self {
+            value.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use http::{header, Method, StatusCode};
+    use tower::{service_fn, Layer, ServiceExt};
+
+    use crate::extension::csrf::CsrfLayer;
+
+    use super::*;
+
+    impl From<CsrfError> for Response<()> {
+        fn from(_: CsrfError) -> Self {
+            Response::builder()
+                .status(StatusCode::FORBIDDEN)
+                .body(())
+                .expect("Valid response")
+        }
+    }
+
+    async fn call(method: Method, headers: &[(&str, &str)]) -> Response<()> {
+        let service = CsrfLayer::new(CsrfConfig::new().allowed_origin("https://app.example.com"))
+            .layer(service_fn(|_: Request<()>| async {
+                Ok::<_, Infallible>(Response::new(()))
+            }));
+
+        let mut request = Request::builder().method(method);
+
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+
+        service
+            .oneshot(request.body(()).expect("Valid request"))
+            .await
+            .expect("Infallible")
+    }
+
+    #[tokio::test]
+    async fn unsafe_requests_need_token_and_origin() {
+        let response = call(Method::GET, &[]).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().contains_key(header::SET_COOKIE));
+
+        let response = call(Method::GET, &[("cookie", "csrf_token=abc")]).await;
+        assert!(!response.headers().contains_key(header::SET_COOKIE));
+
+        let valid = [
+            ("origin", "https://app.example.com"),
+            ("cookie", "csrf_token=abc"),
+            ("x-csrf-token", "abc"),
+        ];
+
+        let response = call(Method::POST, &valid).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = call(Method::POST, &valid[1..]).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = call(
+            Method::POST,
+            &[
+                ("referer", "https://evil.example.com/form"),
+                ("cookie", "csrf_token=abc"),
+                ("x-csrf-token", "abc"),
+            ],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = call(Method::POST, &valid[..2]).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = call(
+            Method::DELETE,
+            &[
+                ("origin", "https://app.example.com"),
+                ("cookie", "csrf_token=abc"),
+                ("x-csrf-token", "abd"),
+            ],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
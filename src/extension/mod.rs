@@ -1,8 +1,20 @@
+mod body;
+mod csrf;
 mod layer;
 mod modify;
+mod policy;
 mod service;
 
 pub use layer::{ExtensionLayer, ExtensionLayerExt};
 pub use service::ExtensionService;
 
 pub use modify::{ModificationLayer, ModificationLayerExt, ModificationService};
+
+pub use csrf::{CsrfConfig, CsrfError, CsrfLayer, CsrfLayerExt, CsrfService, CsrfToken};
+
+pub use policy::{
+    Effect, Policy, PolicyContext, PolicyError, PolicyLayer, PolicyLayerExt, PolicyService, Rule,
+    TimeOfDay,
+};
+
+pub use body::{BodyExtensionLayer, BodyExtensionLayerExt, BodyExtensionService, BufferBodyError};
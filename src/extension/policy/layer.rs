@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use tower::Layer;
+
+use super::{rule::Policy, service::PolicyService};
+
+#[derive(Debug)]
+pub struct PolicyLayer<T> {
+    policy: Arc<Policy<T>>,
+}
+
+impl<T> Clone for PolicyLayer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            policy: self.policy.clone(),
+        }
+    }
+}
+
+impl<T> PolicyLayer<T> {
+    pub fn new(policy: Policy<T>) -> Self {
+        Self {
+            policy: Arc::new(policy),
+        }
+    }
+}
+
+impl<S, T> Layer<S> for PolicyLayer<T> {
+    type Service = PolicyService<S, T>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        PolicyService::new(service, self.policy.clone())
+    }
+}
+
+pub trait PolicyLayerExt<T>: Sized {
+    fn policy_layer(self) -> PolicyLayer<T>;
+}
+
+impl<T> PolicyLayerExt<T> for Policy<T> {
+    fn policy_layer(self) -> PolicyLayer<T> {
+        PolicyLayer::new(self)
+    }
+}
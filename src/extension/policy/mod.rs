@@ -0,0 +1,7 @@
+mod layer;
+mod rule;
+mod service;
+
+pub use layer::{PolicyLayer, PolicyLayerExt};
+pub use rule::{Effect, Policy, PolicyContext, Rule, TimeOfDay};
+pub use service::{PolicyError, PolicyService};
@@ -0,0 +1,457 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use http::{request::Parts, HeaderName, HeaderValue, Method, StatusCode};
+
+/// The outcome of a [`Rule`] or a [`Policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Allow,
+    Deny,
+}
+
+/// A time of day in UTC, with minute precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimeOfDay {
+    minutes: u16,
+}
+
+impl TimeOfDay {
+    /// # Panics
+    ///
+    /// If `hour` is not below 24 or `minute` is not below 60.
+    pub fn new(hour: u8, minute: u8) -> Self {
+        assert!(hour < 24, "Hour must be below 24");
+        assert!(minute < 60, "Minute must be below 60");
+
+        Self {
+            minutes: hour as u16 * 60 + minute as u16,
+        }
+    }
+
+    pub fn now() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        Self {
+            minutes: ((seconds % 86_400) / 60) as u16,
+        }
+    }
+
+    pub fn hour(&self) -> u8 {
+        (self.minutes / 60) as u8
+    }
+
+    pub fn minute(&self) -> u8 {
+        (self.minutes % 60) as u8
+    }
+
+    /// Whether `self` is in `[start, end)`, wrapping around midnight if `start` is after `end`.
+    fn is_between(&self, start: TimeOfDay, end: TimeOfDay) -> bool {
+        if start <= end {
+            return start <= *self && *self < end;
+        }
+
+        *self >= start || *self < end
+    }
+}
+
+/// What a [`Rule`] sees of a request.
+#[derive(Debug)]
+pub struct PolicyContext<'a, T> {
+    parts: &'a Parts,
+    principal: &'a T,
+    path_params: HashMap<&'a str, &'a str>,
+    time_of_day: TimeOfDay,
+}
+
+impl<'a, T> PolicyContext<'a, T> {
+    pub fn parts(&self) -> &'a Parts {
+        self.parts
+    }
+
+    pub fn principal(&self) -> &'a T {
+        self.principal
+    }
+
+    /// A `{param}` captured by the rule's path template, as it appears in the request path.
+    pub fn path_param(&self, name: &str) -> Option<&'a str> {
+        self.path_params.get(name).copied()
+    }
+
+    pub fn header(&self, name: &str) -> Option<&'a str> {
+        self.parts
+            .headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    }
+
+    pub fn time_of_day(&self) -> TimeOfDay {
+        self.time_of_day
+    }
+}
+
+type Condition<T> = Arc<dyn Fn(&PolicyContext<'_, T>) -> bool + Send + Sync>;
+
+/// An allow or deny decision that applies if all of its conditions hold.
+pub struct Rule<T> {
+    name: String,
+    effect: Effect,
+    methods: Vec<Method>,
+    path: Option<Vec<String>>,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    time_of_day: Option<(TimeOfDay, TimeOfDay)>,
+    conditions: Vec<Condition<T>>,
+}
+
+impl<T> std::fmt::Debug for Rule<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rule")
+            .field("name", &self.name)
+            .field("effect", &self.effect)
+            .field("methods", &self.methods)
+            .field("path", &self.path)
+            .field("headers", &self.headers)
+            .field("time_of_day", &self.time_of_day)
+            .field("conditions", &self.conditions.len())
+            .finish()
+    }
+}
+
+impl<T> Rule<T> {
+    fn new(name: impl Into<String>, effect: Effect) -> Self {
+        Self {
+            name: name.into(),
+            effect,
+            methods: Vec::new(),
+            path: None,
+            headers: Vec::new(),
+            time_of_day: None,
+            conditions: Vec::new(),
+        }
+    }
+
+    pub fn allow(name: impl Into<String>) -> Self {
+        Self::new(name, Effect::Allow)
+    }
+
+    pub fn deny(name: impl Into<String>) -> Self {
+        Self::new(name, Effect::Deny)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn effect(&self) -> Effect {
+        self.effect
+    }
+
+    /// May be called multiple times to allow several methods.
+    pub fn method(mut self, method: Method) -> Self {
+        self.methods.push(method);
+        self
+    }
+
+    /// Segments in braces (`{tenant}`) are captured as path params, `*` matches any segment.
+    ///
+    /// The raw segments of the request path are matched, as the router sees them.
+    /// Paths with dot or encoded-slash segments are denied by the [`Policy`] before any rule is tried.
+    pub fn path(mut self, template: &str) -> Self {
+        self.path = Some(segments(template).map(ToString::to_string).collect());
+        self
+    }
+
+    pub fn header_equals(mut self, name: &str, value: &str) -> Result<Self, http::Error> {
+        let name = HeaderName::try_from(name)?;
+        let value = HeaderValue::try_from(value)?;
+
+        self.headers.push((name, value));
+
+        Ok(self)
+    }
+
+    /// The UTC time window `[start, end)`, which may wrap around midnight.
+    pub fn time_of_day(mut self, start: TimeOfDay, end: TimeOfDay) -> Self {
+        self.time_of_day = Some((start, end));
+        self
+    }
+
+    pub fn when<F>(mut self, condition: F) -> Self
+    where
+        F: Fn(&PolicyContext<'_, T>) -> bool + Send + Sync + 'static,
+    {
+        self.conditions.push(Arc::new(condition));
+        self
+    }
+
+    fn match_path<'a>(&'a self, path: &'a str) -> Option<HashMap<&'a str, &'a str>> {
+        let mut params = HashMap::new();
+
+        let Some(template) = &self.path else {
+            return Some(params);
+        };
+
+        let mut segments = raw_segments(path);
+
+        for expected in template {
+            let segment = segments.next()?;
+
+            if let Some(param) = expected
+                .strip_prefix('{')
+                .and_then(|param| param.strip_suffix('}'))
+            {
+                params.insert(param, segment);
+                continue;
+            }
+
+            if expected != "*" && expected != segment {
+                return None;
+            }
+        }
+
+        if segments.next().is_some() {
+            return None;
+        }
+
+        Some(params)
+    }
+
+    fn applies(&self, parts: &Parts, principal: &T, time_of_day: TimeOfDay) -> bool {
+        if !self.methods.is_empty() && !self.methods.contains(&parts.method) {
+            return false;
+        }
+
+        if let Some((start, end)) = self.time_of_day {
+            if !time_of_day.is_between(start, end) {
+                return false;
+            }
+        }
+
+        if !self
+            .headers
+            .iter()
+            .all(|(name, value)| parts.headers.get(name) == Some(value))
+        {
+            return false;
+        }
+
+        let Some(path_params) = self.match_path(parts.uri.path()) else {
+            return false;
+        };
+
+        let context = PolicyContext {
+            parts,
+            principal,
+            path_params,
+            time_of_day,
+        };
+
+        self.conditions.iter().all(|condition| condition(&context))
+    }
+}
+
+fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+/// The segments of a request path, including empty ones.
+fn raw_segments(path: &str) -> impl Iterator<Item = &str> {
+    path.strip_prefix('/').unwrap_or(path).split('/')
+}
+
+/// Whether a segment of the request path may be resolved differently by the router and the application:
+/// `.` and `..`, also percent-encoded, and segments containing an encoded slash or backslash.
+fn is_ambiguous(segment: &str) -> bool {
+    let segment = segment.to_ascii_lowercase();
+
+    if segment.contains("%2f") || segment.contains("%5c") {
+        return true;
+    }
+
+    matches!(segment.replace("%2e", ".").as_str(), "." | "..")
+}
+
+/// An ordered list of [`Rule`]s evaluated by the [`PolicyLayer`](super::PolicyLayer).
+///
+/// The first applying rule decides. If no rule applies, the request is denied.
+/// Requests whose path has a `.`, `..` or encoded-slash segment are always denied.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let policy = Policy::new("orders")
+///     .rule(Rule::deny("maintenance").time_of_day(TimeOfDay::new(2, 0), TimeOfDay::new(3, 0)))
+///     .rule(Rule::allow("read").method(Method::GET).path("/orders/{id}"))
+///     .deny_status(StatusCode::NOT_FOUND);
+/// ```
+#[derive(Debug)]
+pub struct Policy<T> {
+    name: String,
+    rules: Vec<Rule<T>>,
+    default_effect: Effect,
+    deny_status: StatusCode,
+    deny_message: Option<String>,
+}
+
+impl<T> Policy<T> {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            rules: Vec::new(),
+            default_effect: Effect::Deny,
+            deny_status: StatusCode::FORBIDDEN,
+            deny_message: None,
+        }
+    }
+
+    pub fn rule(mut self, rule: Rule<T>) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// The effect if no rule applies. Defaults to [`Effect::Deny`].
+    pub fn default_effect(mut self, effect: Effect) -> Self {
+        self.default_effect = effect;
+        self
+    }
+
+    /// The status of deny responses. Defaults to `403 Forbidden`.
+    pub fn deny_status(mut self, status: StatusCode) -> Self {
+        self.deny_status = status;
+        self
+    }
+
+    /// Defaults to an empty body.
+    pub fn deny_message(mut self, message: impl Into<String>) -> Self {
+        self.deny_message = Some(message.into());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn status(&self) -> StatusCode {
+        self.deny_status
+    }
+
+    pub(crate) fn message(&self) -> Option<&str> {
+        self.deny_message.as_deref()
+    }
+
+    /// Returns the decision and the name of the rule that made it, if any.
+    pub fn evaluate(&self, parts: &Parts, principal: &T) -> (Effect, Option<&str>) {
+        if raw_segments(parts.uri.path()).any(is_ambiguous) {
+            return (Effect::Deny, None);
+        }
+
+        let time_of_day = TimeOfDay::now();
+
+        self.rules
+            .iter()
+            .find(|rule| rule.applies(parts, principal, time_of_day))
+            .map(|rule| (rule.effect, Some(rule.name.as_str())))
+            .unwrap_or((self.default_effect, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Request;
+
+    use super::*;
+
+    struct Principal {
+        tenant: String,
+    }
+
+    fn parts(method: Method, path: &str) -> Parts {
+        Request::builder()
+            .method(method)
+            .uri(path)
+            .header("x-tenant", "acme")
+            .body(())
+            .expect("Valid request")
+            .into_parts()
+            .0
+    }
+
+    #[test]
+    fn first_applying_rule_decides() {
+        let policy = Policy::new("orders")
+            .rule(Rule::deny("no-deletes").method(Method::DELETE))
+            .rule(
+                Rule::allow("own-tenant")
+                    .path("/tenants/{tenant}/orders/*")
+                    .header_equals("x-tenant", "acme")
+                    .expect("Valid header")
+                    .when(|ctx: &PolicyContext<'_, Principal>| {
+                        ctx.path_param("tenant") == Some(ctx.principal().tenant.as_str())
+                    }),
+            );
+
+        let principal = Principal {
+            tenant: String::from("acme"),
+        };
+
+        let decision = policy.evaluate(&parts(Method::GET, "/tenants/acme/orders/1"), &principal);
+        assert_eq!(decision, (Effect::Allow, Some("own-tenant")));
+
+        let decision =
+            policy.evaluate(&parts(Method::DELETE, "/tenants/acme/orders/1"), &principal);
+        assert_eq!(decision, (Effect::Deny, Some("no-deletes")));
+
+        let decision = policy.evaluate(&parts(Method::GET, "/tenants/other/orders/1"), &principal);
+        assert_eq!(decision, (Effect::Deny, None));
+
+        let decision = policy.evaluate(&parts(Method::GET, "/tenants/acme/orders"), &principal);
+        assert_eq!(decision, (Effect::Deny, None));
+
+        assert!(Rule::<Principal>::allow("invalid")
+            .header_equals("x tenant", "acme")
+            .is_err());
+    }
+
+    #[test]
+    fn raw_path_segments_are_matched() {
+        let policy = Policy::<()>::new("public")
+            .rule(Rule::deny("admin").path("/admin/{section}"))
+            .rule(Rule::allow("public").path("/public/*"));
+
+        assert_eq!(
+            policy.evaluate(&parts(Method::GET, "/public/x"), &()),
+            (Effect::Allow, Some("public"))
+        );
+
+        for path in [
+            "/admin/../public/x",
+            "/public/./x",
+            "/public/%2e%2E/x",
+            "/public/a%2Fb",
+            "//public/x",
+            "/%70ublic/x",
+        ] {
+            assert_eq!(
+                policy.evaluate(&parts(Method::GET, path), &()),
+                (Effect::Deny, None),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn time_windows_wrap_around_midnight() {
+        let (start, end) = (TimeOfDay::new(22, 0), TimeOfDay::new(6, 0));
+
+        assert!(TimeOfDay::new(23, 30).is_between(start, end));
+        assert!(TimeOfDay::new(5, 59).is_between(start, end));
+        assert!(!TimeOfDay::new(6, 0).is_between(start, end));
+        assert!(!TimeOfDay::new(12, 0).is_between(start, end));
+    }
+}
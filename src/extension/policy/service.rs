@@ -0,0 +1,131 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use http::{Request, StatusCode};
+use tower::Service;
+
+use crate::extract::SealedExtracted;
+
+use super::rule::{Effect, Policy};
+
+#[derive(Debug)]
+pub struct PolicyService<S, T> {
+    service: S,
+    policy: Arc<Policy<T>>,
+}
+
+impl<S, T> Clone for PolicyService<S, T>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            service: self.service.clone(),
+            policy: self.policy.clone(),
+        }
+    }
+}
+
+impl<S, T> PolicyService<S, T> {
+    pub fn new(service: S, policy: Arc<Policy<T>>) -> Self {
+        Self { service, policy }
+    }
+}
+
+impl<S, T, B> Service<Request<B>> for PolicyService<S, T>
+where
+    S: Service<Request<B>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Response: From<PolicyError>,
+    T: Send + Sync + 'static,
+    B: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let mut service = self.service.clone();
+        let policy = self.policy.clone();
+
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+
+            let Some(principal) = parts.extensions.get::<SealedExtracted<T>>() else {
+                return Ok(From::from(PolicyError::Extract));
+            };
+
+            let (effect, rule) = policy.evaluate(&parts, principal);
+
+            tracing::info!(
+                policy = %policy.name(),
+                rule = ?rule,
+                ?effect,
+                method = %parts.method,
+                path = %parts.uri.path(),
+                "Policy decision"
+            );
+
+            if effect == Effect::Deny {
+                return Ok(From::from(PolicyError::Denied {
+                    policy: policy.name().to_string(),
+                    rule: rule.map(ToString::to_string),
+                    status: policy.status(),
+                    message: policy.message().map(ToString::to_string),
+                }));
+            }
+
+            service.call(Request::from_parts(parts, body)).await
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PolicyError {
+    #[error("Extraction error")]
+    Extract,
+    #[error("Denied by policy {policy}")]
+    Denied {
+        policy: String,
+        /// The rule that denied the request, or `None` if no rule matched.
+        rule: Option<String>,
+        status: StatusCode,
+        message: Option<String>,
+    },
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use axum::response::{IntoResponse, Response};
+    use http::StatusCode;
+
+    use super::PolicyError;
+
+    impl IntoResponse for PolicyError {
+        fn into_response(self) -> Response {
+            match self {
+                PolicyError::Extract => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+                PolicyError::Denied {
+                    status, message, ..
+                } => match message {
+                    Some(message) => (status, message).into_response(),
+                    None => status.into_response(),
+                },
+            }
+        }
+    }
+
+    impl From<PolicyError> for Response {
+        fn from(value: PolicyError) -> Self {
+            value.into_response()
+        }
+    }
+}
@@ -37,21 +37,21 @@ where
         self.service.poll_ready(cx)
     }
 
-    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+    fn call(&mut self, request: Request<B>) -> Self::Future {
         let mut service = self.service.clone();
         let extractor = self.extractor.clone();
 
         Box::pin(async move {
-            let headers = request.headers();
+            let (mut parts, body) = request.into_parts();
 
-            let extracted = match extractor.extract(headers).await {
+            let extracted = match extractor.extract(&parts).await {
                 Ok(extracted) => extracted,
                 Err(err) => return Ok(From::from(err)),
             };
 
-            request.extensions_mut().insert(SealedExtracted(extracted));
+            parts.extensions.insert(SealedExtracted(extracted));
 
-            service.call(request).await
+            service.call(Request::from_parts(parts, body)).await
         })
     }
 }
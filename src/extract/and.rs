@@ -27,9 +27,9 @@ where
 
     type Error = AndError<L::Error, R::Error>;
 
-    async fn extract(&self, headers: &http::HeaderMap) -> Result<Self::Extracted, Self::Error> {
-        let left = self.left.extract(headers).await.map_err(AndError::Left)?;
-        let right = self.right.extract(headers).await.map_err(AndError::Right)?;
+    async fn extract(&self, parts: &http::request::Parts) -> Result<Self::Extracted, Self::Error> {
+        let left = self.left.extract(parts).await.map_err(AndError::Left)?;
+        let right = self.right.extract(parts).await.map_err(AndError::Right)?;
 
         Ok(And { left, right })
     }
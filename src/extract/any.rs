@@ -23,10 +23,10 @@ where
 
     type Error = AnyError<L::Error, R::Error>;
 
-    async fn extract(&self, headers: &http::HeaderMap) -> Result<Self::Extracted, Self::Error> {
-        match self.left.extract(headers).await {
+    async fn extract(&self, parts: &http::request::Parts) -> Result<Self::Extracted, Self::Error> {
+        match self.left.extract(parts).await {
             Ok(extracted) => Ok(extracted),
-            Err(left_error) => match self.right.extract(headers).await {
+            Err(left_error) => match self.right.extract(parts).await {
                 Ok(extracted) => Ok(extracted.into()),
                 Err(right_error) => Err(AnyError {
                     left: left_error,
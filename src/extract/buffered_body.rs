@@ -0,0 +1,37 @@
+use std::ops::Deref;
+
+use bytes::Bytes;
+use http::request::Parts;
+
+/// The request body, buffered by a [`BodyExtensionLayer`](crate::extension::BodyExtensionLayer)
+/// and made available to extractors through the request extensions.
+#[derive(Debug, Clone)]
+pub struct BufferedBody(pub(crate) Bytes);
+
+impl BufferedBody {
+    pub fn from_parts(parts: &Parts) -> Option<&Self> {
+        parts.extensions.get::<Self>()
+    }
+
+    pub fn bytes(&self) -> &Bytes {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> Bytes {
+        self.0
+    }
+}
+
+impl Deref for BufferedBody {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for BufferedBody {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
@@ -0,0 +1,536 @@
+use std::{
+    borrow::Cow,
+    num::NonZeroUsize,
+    ops::Deref,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+
+use http::request::Parts;
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+
+use super::extractor::Extractor;
+
+type ExpiresAt<T> = Arc<dyn Fn(&T) -> Option<SystemTime> + Send + Sync>;
+
+type VaryBy = Arc<dyn Fn(&Parts) -> Option<Vec<u8>> + Send + Sync>;
+
+/// Configuration for [`Cached`].
+///
+/// Entries are keyed by a SHA-256 hash of the configured header value.
+/// Requests without the header bypass the cache.
+///
+/// A cached result is served for every request with the same header value.
+/// If the wrapped extractor also depends on other parts of the request, e.g. the method or the path,
+/// add them to the key with [`CacheConfig::vary_by`].
+pub struct CacheConfig<T> {
+    header_name: Cow<'static, str>,
+    capacity: NonZeroUsize,
+    time_to_live: Duration,
+    negative_time_to_live: Option<Duration>,
+    expires_at: Option<ExpiresAt<T>>,
+    vary_by: Option<VaryBy>,
+}
+
+impl<T> core::fmt::Debug for CacheConfig<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheConfig")
+            .field("header_name", &self.header_name)
+            .field("capacity", &self.capacity)
+            .field("time_to_live", &self.time_to_live)
+            .field("negative_time_to_live", &self.negative_time_to_live)
+            .field("expires_at", &self.expires_at.as_ref().map(|_| "..."))
+            .field("vary_by", &self.vary_by.as_ref().map(|_| "..."))
+            .finish()
+    }
+}
+
+impl<T> Clone for CacheConfig<T> {
+    fn clone(&self) -> Self {
+        Self {
+            header_name: self.header_name.clone(),
+            capacity: self.capacity,
+            time_to_live: self.time_to_live,
+            negative_time_to_live: self.negative_time_to_live,
+            expires_at: self.expires_at.clone(),
+            vary_by: self.vary_by.clone(),
+        }
+    }
+}
+
+impl<T> CacheConfig<T> {
+    pub fn new(header_name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            header_name: header_name.into(),
+            capacity: NonZeroUsize::new(1024).expect("1024 is not zero"),
+            time_to_live: Duration::from_secs(60),
+            negative_time_to_live: None,
+            expires_at: None,
+            vary_by: None,
+        }
+    }
+
+    pub fn capacity(mut self, capacity: NonZeroUsize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn time_to_live_in_seconds(mut self, time_to_live_in_seconds: u64) -> Self {
+        self.time_to_live = Duration::from_secs(time_to_live_in_seconds);
+        self
+    }
+
+    /// Caches failed extractions for the given duration.
+    ///
+    /// Every error is cached, including transient ones like a failing JWK set fetch,
+    /// so keep this short.
+    pub fn negative_time_to_live_in_seconds(
+        mut self,
+        negative_time_to_live_in_seconds: u64,
+    ) -> Self {
+        self.negative_time_to_live = Some(Duration::from_secs(negative_time_to_live_in_seconds));
+        self
+    }
+
+    /// Caps the lifetime of an entry by the expiry of the extracted value, e.g. the `exp` claim of a JWT.
+    pub fn expires_at<Fn>(mut self, expires_at: Fn) -> Self
+    where
+        Fn: core::ops::Fn(&T) -> Option<SystemTime> + Send + Sync + 'static,
+    {
+        self.expires_at = Some(Arc::new(expires_at));
+        self
+    }
+
+    /// Adds the returned bytes to the cache key. Requests for which `vary_by` returns `None` bypass the cache.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,ignore
+    /// let config = CacheConfig::new("authorization").vary_by(|parts| {
+    ///     Some(format!("{} {}", parts.method, parts.uri.path()).into_bytes())
+    /// });
+    /// ```
+    pub fn vary_by<Fn>(mut self, vary_by: Fn) -> Self
+    where
+        Fn: core::ops::Fn(&Parts) -> Option<Vec<u8>> + Send + Sync + 'static,
+    {
+        self.vary_by = Some(Arc::new(vary_by));
+        self
+    }
+}
+
+/// `expires_at` is `None` if the time to live is too large to represent, in which case the entry never expires.
+#[derive(Debug)]
+enum CacheEntry<T> {
+    Extracted {
+        value: T,
+        expires_at: Option<Instant>,
+    },
+    Rejected {
+        expires_at: Option<Instant>,
+    },
+}
+
+impl<T> CacheEntry<T> {
+    fn is_expired(&self, now: Instant) -> bool {
+        let expires_at = match self {
+            CacheEntry::Extracted { expires_at, .. } => expires_at,
+            CacheEntry::Rejected { expires_at } => expires_at,
+        };
+
+        expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+pub struct CachedInner<Ex>
+where
+    Ex: Extractor,
+{
+    extractor: Ex,
+    config: CacheConfig<Ex::Extracted>,
+    cache: Mutex<LruCache<[u8; 32], CacheEntry<Ex::Extracted>>>,
+}
+
+impl<Ex> core::fmt::Debug for CachedInner<Ex>
+where
+    Ex: Extractor + core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedInner")
+            .field("extractor", &self.extractor)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Ex> CachedInner<Ex>
+where
+    Ex: Extractor,
+{
+    pub fn new(extractor: Ex, config: CacheConfig<Ex::Extracted>) -> Self {
+        Self {
+            cache: Mutex::new(LruCache::new(config.capacity)),
+            extractor,
+            config,
+        }
+    }
+
+    fn key(&self, parts: &Parts) -> Option<[u8; 32]> {
+        let value = parts.headers.get(self.config.header_name.as_ref())?;
+
+        let mut hasher = Sha256::new();
+
+        hasher.update((value.len() as u64).to_be_bytes());
+        hasher.update(value.as_bytes());
+
+        if let Some(vary_by) = &self.config.vary_by {
+            hasher.update(vary_by(parts)?);
+        }
+
+        Some(hasher.finalize().into())
+    }
+
+    fn lookup(&self, key: &[u8; 32]) -> Option<Result<Ex::Extracted, CachedError<Ex::Error>>> {
+        let mut cache = self.cache.lock().unwrap_or_else(|err| err.into_inner());
+
+        let entry = cache.get(key)?;
+
+        if entry.is_expired(Instant::now()) {
+            cache.pop(key);
+
+            return None;
+        }
+
+        match entry {
+            CacheEntry::Extracted { value, .. } => Some(Ok(value.clone())),
+            CacheEntry::Rejected { .. } => Some(Err(CachedError::Rejected)),
+        }
+    }
+
+    fn store(&self, key: [u8; 32], entry: CacheEntry<Ex::Extracted>) {
+        if entry.is_expired(Instant::now()) {
+            return;
+        }
+
+        self.cache
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .put(key, entry);
+    }
+
+    fn expires_at(&self, value: &Ex::Extracted) -> Option<Instant> {
+        let now = Instant::now();
+        let expires_at = now.checked_add(self.config.time_to_live);
+
+        let value_expires_at = self
+            .config
+            .expires_at
+            .as_ref()
+            .and_then(|expires_at| expires_at(value))
+            .and_then(|value_expires_at| {
+                let remaining = value_expires_at
+                    .duration_since(SystemTime::now())
+                    .unwrap_or_default();
+
+                now.checked_add(remaining)
+            });
+
+        match (expires_at, value_expires_at) {
+            (Some(expires_at), Some(value_expires_at)) => Some(expires_at.min(value_expires_at)),
+            (expires_at, value_expires_at) => expires_at.or(value_expires_at),
+        }
+    }
+
+    /// Removes all cached entries.
+    pub fn clear(&self) {
+        self.cache
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clear();
+    }
+}
+
+#[derive(Debug)]
+pub struct Cached<Ex>
+where
+    Ex: Extractor,
+{
+    inner: Arc<CachedInner<Ex>>,
+}
+
+impl<Ex> Cached<Ex>
+where
+    Ex: Extractor,
+{
+    pub fn new(extractor: Ex, config: CacheConfig<Ex::Extracted>) -> Self {
+        Self {
+            inner: Arc::new(CachedInner::new(extractor, config)),
+        }
+    }
+}
+
+impl<Ex> Clone for Cached<Ex>
+where
+    Ex: Extractor,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<Ex> Deref for Cached<Ex>
+where
+    Ex: Extractor,
+{
+    type Target = CachedInner<Ex>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<Ex> Extractor for Cached<Ex>
+where
+    Ex: Extractor + Send + Sync,
+    Ex::Extracted: 'static,
+    Ex::Error: Send,
+{
+    type Extracted = Ex::Extracted;
+
+    type Error = CachedError<Ex::Error>;
+
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+        let Some(key) = self.key(parts) else {
+            return self
+                .extractor
+                .extract(parts)
+                .await
+                .map_err(CachedError::Extract);
+        };
+
+        if let Some(cached) = self.lookup(&key) {
+            tracing::trace!("Cache hit");
+
+            return cached;
+        }
+
+        match self.extractor.extract(parts).await {
+            Ok(extracted) => {
+                let expires_at = self.expires_at(&extracted);
+
+                self.store(
+                    key,
+                    CacheEntry::Extracted {
+                        value: extracted.clone(),
+                        expires_at,
+                    },
+                );
+
+                Ok(extracted)
+            }
+            Err(err) => {
+                if let Some(negative_time_to_live) = self.config.negative_time_to_live {
+                    self.store(
+                        key,
+                        CacheEntry::Rejected {
+                            expires_at: Instant::now().checked_add(negative_time_to_live),
+                        },
+                    );
+                }
+
+                Err(CachedError::Extract(err))
+            }
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CachedError<E> {
+    #[error("Extraction error: {0}")]
+    Extract(#[source] E),
+    #[error("Rejected by a cached negative result")]
+    Rejected,
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use axum::response::{IntoResponse, Response};
+    use http::StatusCode;
+
+    use super::CachedError;
+
+    impl<E> IntoResponse for CachedError<E>
+    where
+        E: IntoResponse,
+    {
+        fn into_response(self) -> Response {
+            match self {
+                CachedError::Extract(err) => err.into_response(),
+                CachedError::Rejected => StatusCode::UNAUTHORIZED.into_response(),
+            }
+        }
+    }
+
+    impl<E> From<CachedError<E>> for Response
+    where
+        E: IntoResponse,
+    {
+        fn from(value: CachedError<E>) -> Self {
+            value.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use http::Request;
+
+    use super::*;
+
+    #[derive(Debug, Clone, Default)]
+    struct CountingExtractor {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl CountingExtractor {
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    impl Extractor for CountingExtractor {
+        type Extracted = String;
+
+        type Error = ();
+
+        async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+
+            match parts.headers.get("x-token").map(|value| value.as_bytes()) {
+                Some(b"valid") => Ok(String::from("principal")),
+                _ => Err(()),
+            }
+        }
+    }
+
+    fn parts(token: &str) -> Parts {
+        Request::builder()
+            .header("x-token", token)
+            .body(())
+            .expect("Valid request")
+            .into_parts()
+            .0
+    }
+
+    #[tokio::test]
+    async fn extracted_value_is_cached() {
+        let extractor = CountingExtractor::default();
+        let cached = Cached::new(extractor.clone(), CacheConfig::new("x-token"));
+
+        for _ in 0..3 {
+            let extracted = cached.extract(&parts("valid")).await.expect("Valid token");
+
+            assert_eq!(extracted, "principal");
+        }
+
+        assert_eq!(extractor.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn key_varies_by_configured_parts() {
+        let extractor = CountingExtractor::default();
+        let cached = Cached::new(
+            extractor.clone(),
+            CacheConfig::new("x-token").vary_by(|parts| Some(parts.uri.path().as_bytes().to_vec())),
+        );
+
+        let mut other_path = parts("valid");
+        other_path.uri = http::Uri::from_static("/other");
+
+        cached.extract(&parts("valid")).await.expect("Valid token");
+        cached.extract(&parts("valid")).await.expect("Valid token");
+        cached.extract(&other_path).await.expect("Valid token");
+
+        assert_eq!(extractor.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn expired_value_is_not_served() {
+        let extractor = CountingExtractor::default();
+        let cached = Cached::new(
+            extractor.clone(),
+            CacheConfig::new("x-token").expires_at(|_| Some(SystemTime::now())),
+        );
+
+        cached.extract(&parts("valid")).await.expect("Valid token");
+        cached.extract(&parts("valid")).await.expect("Valid token");
+
+        assert_eq!(extractor.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn errors_are_cached_only_when_configured() {
+        let extractor = CountingExtractor::default();
+        let cached = Cached::new(extractor.clone(), CacheConfig::new("x-token"));
+
+        assert!(cached.extract(&parts("invalid")).await.is_err());
+        assert!(cached.extract(&parts("invalid")).await.is_err());
+        assert_eq!(extractor.calls(), 2);
+
+        let extractor = CountingExtractor::default();
+        let cached = Cached::new(
+            extractor.clone(),
+            CacheConfig::new("x-token").negative_time_to_live_in_seconds(10),
+        );
+
+        assert!(matches!(
+            cached.extract(&parts("invalid")).await,
+            Err(CachedError::Extract(()))
+        ));
+        assert!(matches!(
+            cached.extract(&parts("invalid")).await,
+            Err(CachedError::Rejected)
+        ));
+        assert_eq!(extractor.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn least_recently_used_entry_is_evicted() {
+        let extractor = CountingExtractor::default();
+        let cached = Cached::new(
+            extractor.clone(),
+            CacheConfig::new("x-token")
+                .capacity(NonZeroUsize::new(1).expect("1 is not zero"))
+                .negative_time_to_live_in_seconds(10),
+        );
+
+        cached.extract(&parts("valid")).await.expect("Valid token");
+        let _ = cached.extract(&parts("invalid")).await;
+        cached.extract(&parts("valid")).await.expect("Valid token");
+
+        assert_eq!(extractor.calls(), 3);
+    }
+
+    #[tokio::test]
+    async fn huge_time_to_live_never_expires() {
+        let extractor = CountingExtractor::default();
+        let cached = Cached::new(
+            extractor.clone(),
+            CacheConfig::new("x-token")
+                .time_to_live_in_seconds(u64::MAX)
+                .negative_time_to_live_in_seconds(u64::MAX),
+        );
+
+        for _ in 0..3 {
+            assert!(cached.extract(&parts("valid")).await.is_ok());
+            assert!(cached.extract(&parts("invalid")).await.is_err());
+        }
+
+        assert_eq!(extractor.calls(), 2);
+    }
+}
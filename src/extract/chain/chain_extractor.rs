@@ -54,10 +54,10 @@ where
 
     type Error = ChainError<Ex::Error, C::Error>;
 
-    async fn extract(&self, headers: &http::HeaderMap) -> Result<Self::Extracted, Self::Error> {
+    async fn extract(&self, parts: &http::request::Parts) -> Result<Self::Extracted, Self::Error> {
         let extracted = self
             .extractor
-            .extract(headers)
+            .extract(parts)
             .await
             .map_err(ChainError::Extract)?;
 
@@ -1,6 +1,6 @@
 use std::future::Future;
 
-use http::HeaderMap;
+use http::request::Parts;
 
 use crate::extract::extractor::Extractor;
 
@@ -39,8 +39,8 @@ where
 
     type Error = E;
 
-    async fn extract(&self, headers: &HeaderMap) -> Result<Self::Extracted, Self::Error> {
-        let ex = self.inner.extract(headers).await?;
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+        let ex = self.inner.extract(parts).await?;
 
         (self.chain.clone())(ex)
     }
@@ -58,8 +58,8 @@ where
 
     type Error = E;
 
-    async fn extract(&self, headers: &HeaderMap) -> Result<Self::Extracted, Self::Error> {
-        let ex = self.inner.extract(headers).await?;
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+        let ex = self.inner.extract(parts).await?;
 
         (self.chain.clone())(ex).await
     }
@@ -1,6 +1,6 @@
 use std::future::Future;
 
-use http::HeaderMap;
+use http::request::Parts;
 
 use super::extractor::Extractor;
 
@@ -38,8 +38,8 @@ where
 
     type Error = E;
 
-    async fn extract(&self, headers: &HeaderMap) -> Result<Self::Extracted, Self::Error> {
-        let ex = self.inner.extract(headers).await;
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+        let ex = self.inner.extract(parts).await;
 
         (self.convert.clone())(ex)
     }
@@ -56,8 +56,8 @@ where
 
     type Error = E;
 
-    async fn extract(&self, headers: &HeaderMap) -> Result<Self::Extracted, Self::Error> {
-        let ex = self.inner.extract(headers).await;
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+        let ex = self.inner.extract(parts).await;
 
         (self.convert.clone())(ex).await
     }
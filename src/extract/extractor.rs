@@ -1,6 +1,6 @@
 use std::future::Future;
 
-use http::HeaderMap;
+use http::request::Parts;
 
 use super::chain::chain_extractor::ChainExtractor;
 
@@ -8,12 +8,14 @@ use super::chain::lite::{AsyncChainLite, ChainLite};
 use super::{
     and::AndExtractor,
     any::Any,
+    cached::{CacheConfig, Cached},
     convert::{AsyncConvert, Convert},
     map::{AsyncMap, Map, MapError},
     optional::Optional,
     or::OrExtractor,
 };
 
+/// Extracts a value from the request head (method, uri, version, headers and extensions).
 pub trait Extractor {
     type Extracted: Clone + Send + Sync;
 
@@ -21,7 +23,7 @@ pub trait Extractor {
 
     fn extract(
         &self,
-        headers: &HeaderMap,
+        parts: &Parts,
     ) -> impl Future<Output = Result<Self::Extracted, Self::Error>> + Send;
 
     fn extracted_type_name(&self) -> &'static str {
@@ -62,6 +64,10 @@ pub trait ExtractorExt: Sized + Extractor {
         AsyncChainLite::new(self, chain)
     }
 
+    fn cached(self, config: CacheConfig<Self::Extracted>) -> Cached<Self> {
+        Cached::new(self, config)
+    }
+
     fn optional(self) -> Optional<Self> {
         Optional::new(self)
     }
@@ -1,6 +1,6 @@
 use std::future::Future;
 
-use http::HeaderMap;
+use http::request::Parts;
 
 use super::extractor::Extractor;
 
@@ -50,9 +50,9 @@ where
 
     type Error = Ex::Error;
 
-    async fn extract(&self, headers: &HeaderMap) -> Result<Self::Extracted, Self::Error> {
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
         self.inner
-            .extract(headers)
+            .extract(parts)
             .await
             .map(|ex| (self.map.clone())(ex))
     }
@@ -69,8 +69,8 @@ where
 
     type Error = Ex::Error;
 
-    async fn extract(&self, headers: &HeaderMap) -> Result<Self::Extracted, Self::Error> {
-        let extracted = self.inner.extract(headers).await?;
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+        let extracted = self.inner.extract(parts).await?;
 
         let mapped = (self.map.clone())(extracted).await;
 
@@ -87,9 +87,9 @@ where
 
     type Error = E;
 
-    async fn extract(&self, headers: &HeaderMap) -> Result<Self::Extracted, Self::Error> {
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
         self.inner
-            .extract(headers)
+            .extract(parts)
             .await
             .map_err(|err| (self.map_err.clone())(err))
     }
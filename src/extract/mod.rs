@@ -1,5 +1,7 @@
 mod and;
 mod any;
+mod buffered_body;
+mod cached;
 mod chain;
 mod convert;
 mod extracted;
@@ -11,6 +13,8 @@ mod sealed_extracted;
 
 pub use and::{And, AndError};
 pub use any::{Any, AnyError};
+pub use buffered_body::BufferedBody;
+pub use cached::{CacheConfig, Cached, CachedError};
 pub use chain::{
     chain_extractor::{ChainError, ChainExtractor},
     chainer::Chainer,
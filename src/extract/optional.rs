@@ -1,4 +1,4 @@
-use http::HeaderMap;
+use http::request::Parts;
 
 use crate::error::InfallibleError;
 
@@ -23,7 +23,7 @@ where
 
     type Error = InfallibleError;
 
-    async fn extract(&self, headers: &HeaderMap) -> Result<Self::Extracted, Self::Error> {
-        Ok(self.inner.extract(headers).await.ok())
+    async fn extract(&self, parts: &Parts) -> Result<Self::Extracted, Self::Error> {
+        Ok(self.inner.extract(parts).await.ok())
     }
 }
@@ -28,10 +28,10 @@ where
 
     type Error = OrError<L::Error, R::Error>;
 
-    async fn extract(&self, headers: &http::HeaderMap) -> Result<Self::Extracted, Self::Error> {
-        match self.left.extract(headers).await {
+    async fn extract(&self, parts: &http::request::Parts) -> Result<Self::Extracted, Self::Error> {
+        match self.left.extract(parts).await {
             Ok(extracted) => Ok(Or::Left(extracted)),
-            Err(left_error) => match self.right.extract(headers).await {
+            Err(left_error) => match self.right.extract(parts).await {
                 Ok(extracted) => Ok(Or::Right(extracted)),
                 Err(right_error) => Err(OrError {
                     left: left_error,